@@ -1,5 +1,5 @@
 use serde_json::json;
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Manager, State};
 
 use crate::{
     models::{
@@ -11,6 +11,7 @@ use crate::{
             get_global_config, get_latest_project_source_path, update_project as _update_project, write_config,
         },
         log::log_error,
+        reqwest::HttpClientState,
     },
 };
 
@@ -70,12 +71,21 @@ pub fn load_latest_project(app_handle: tauri::AppHandle) -> Result<WebResponse,
 pub fn update_global_config(
     data: GlobalConfigRequest,
     app_handle: AppHandle,
+    http_client: State<HttpClientState>,
 ) -> Result<WebResponse, String> {
     let config = get_global_config(&app_handle);
     match config {
         Ok(mut global_config) => {
+            let is_network_changed = data.is_network_changed();
             global_config.merge_from_request(data);
 
+            // 先重建客户端，网络配置有误时不写入配置文件
+            if is_network_changed {
+                if let Err(e) = http_client.reload(&global_config) {
+                    return log_error(&app_handle, e.to_string());
+                }
+            }
+
             match write_config(&app_handle, &global_config) {
                 Ok(_) => Ok(WebResponse {
                     message: String::from("更新成功！"),
@@ -1,2 +1,4 @@
 pub mod global_config;
+pub mod openapi;
+pub mod source;
 pub mod yapi;
\ No newline at end of file
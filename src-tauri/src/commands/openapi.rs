@@ -0,0 +1,27 @@
+use serde_json::json;
+use tauri::AppHandle;
+
+use crate::{
+    models::{openapi::OpenApiFormat, web_response::WebResponse},
+    services::{log::log_error, openapi::export::export_openapi_document},
+};
+
+#[tauri::command]
+pub async fn export_openapi(
+    app_handle: AppHandle,
+    source_path: &str,
+    target_path: &str,
+    format: OpenApiFormat,
+) -> Result<WebResponse, String> {
+    match export_openapi_document(source_path, target_path, format, &app_handle).await {
+        Ok(report) => Ok(WebResponse {
+            message: format!(
+                "导出成功 {} 个接口，失败 {} 个",
+                report.exported,
+                report.failed.len()
+            ),
+            data: Some(json!(report)),
+        }),
+        Err(e) => log_error(&app_handle, e.to_string()),
+    }
+}
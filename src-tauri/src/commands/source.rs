@@ -0,0 +1,123 @@
+use std::io;
+
+use serde_json::json;
+use tauri::AppHandle;
+
+use crate::{
+    models::{
+        source::{SourceImportReport, SourceProject},
+        web_response::WebResponse,
+        yapi::config::ProjectSource,
+    },
+    services::{
+        log::{log, log_error},
+        source::{har, merge_source_project_to_project_config, openapi, postman, yapi_dump},
+    },
+};
+
+// 导入本地 OpenAPI 2/3 文件作为项目，file_path 可以是相对代码库根目录的路径
+#[tauri::command]
+pub async fn import_openapi_source(
+    app_handle: AppHandle,
+    source_path: &str,
+    file_path: &str,
+) -> Result<WebResponse, String> {
+    import_source_project(
+        &app_handle,
+        source_path,
+        openapi::load_source_project(source_path, file_path),
+        ProjectSource::OpenApi {
+            file_path: file_path.to_string(),
+        },
+    )
+}
+
+// 导入 yapi 导出的 json 文件作为项目，与线上项目 id 相同时替换其来源
+#[tauri::command]
+pub async fn import_yapi_dump_source(
+    app_handle: AppHandle,
+    source_path: &str,
+    file_path: &str,
+) -> Result<WebResponse, String> {
+    import_source_project(
+        &app_handle,
+        source_path,
+        yapi_dump::load_source_project(source_path, file_path),
+        ProjectSource::YapiDump {
+            file_path: file_path.to_string(),
+        },
+    )
+}
+
+// 导入浏览器导出的 har 文件，接口记录在以文件命名的项目下
+#[tauri::command]
+pub async fn import_har_source(
+    app_handle: AppHandle,
+    source_path: &str,
+    file_path: &str,
+) -> Result<WebResponse, String> {
+    import_source_project(
+        &app_handle,
+        source_path,
+        har::load_source_project(source_path, file_path),
+        ProjectSource::Har {
+            file_path: file_path.to_string(),
+        },
+    )
+}
+
+// 导入 Postman v2.1 collection，重复导入时只追加新的分类与接口
+#[tauri::command]
+pub async fn import_postman_source(
+    app_handle: AppHandle,
+    source_path: &str,
+    file_path: &str,
+) -> Result<WebResponse, String> {
+    import_source_project(
+        &app_handle,
+        source_path,
+        postman::load_source_project(source_path, file_path),
+        ProjectSource::Postman {
+            file_path: file_path.to_string(),
+        },
+    )
+}
+
+fn import_source_project(
+    app_handle: &AppHandle,
+    source_path: &str,
+    source_project: Result<SourceProject, io::Error>,
+    source: ProjectSource,
+) -> Result<WebResponse, String> {
+    let source_project = match source_project {
+        Ok(source_project) => source_project,
+        Err(e) => return log_error(app_handle, e.to_string()),
+    };
+
+    match merge_source_project_to_project_config(source_path, &source_project, source) {
+        Ok(_) => {
+            log(
+                app_handle,
+                format!("更新项目{}至配置文件成功", source_project.name),
+            );
+            Ok(WebResponse {
+                message: format!("导入项目{}成功", source_project.name),
+                data: Some(json!(get_import_report(&source_project))),
+            })
+        }
+        Err(e) => log_error(app_handle, e.to_string()),
+    }
+}
+
+fn get_import_report(source_project: &SourceProject) -> SourceImportReport {
+    SourceImportReport {
+        project_id: source_project.project_id.to_string(),
+        project_name: source_project.name.clone(),
+        categories: source_project.categories.len(),
+        interfaces: source_project
+            .categories
+            .iter()
+            .map(|category| category.interfaces.len())
+            .sum(),
+    }
+}
@@ -0,0 +1,118 @@
+use serde_json::json;
+use tauri::AppHandle;
+
+use crate::{
+    models::{
+        web_response::WebResponse,
+        yapi::{cache::CacheRegenerateReport, queue::queue_log},
+    },
+    services::{
+        log::{log, log_error},
+        yapi::{
+            cache::{self, invalidate_cache, read_cache_entry},
+            config::get_project_config,
+            interface::get_resolved_interface,
+        },
+    },
+};
+
+// 不访问网络，用缓存的接口详情重新生成，结果与跑批任务一样通过 queue_log 推送
+#[tauri::command]
+pub fn regenerate_from_cache(
+    app_handle: AppHandle,
+    source_path: &str,
+    project_id: Option<String>,
+) -> Result<WebResponse, String> {
+    let project_config = match get_project_config(source_path) {
+        Ok(project_config) => project_config,
+        Err(e) => return log_error(&app_handle, e.to_string()),
+    };
+    let mut report = CacheRegenerateReport::default();
+
+    for project in &project_config.project_list {
+        if project_id
+            .as_ref()
+            .map_or(false, |project_id| *project_id != project.project_id)
+        {
+            continue;
+        }
+
+        for interface in project
+            .categories
+            .iter()
+            .flat_map(|category| &category.interfaces)
+        {
+            report.total += 1;
+
+            let cached_interface =
+                match read_cache_entry(source_path, &project.project_id, &interface.id) {
+                    Ok(Some(cached_interface)) => cached_interface,
+                    Ok(None) => {
+                        report.missing.push(interface.name.clone());
+                        continue;
+                    }
+                    Err(e) => {
+                        report.failed.push(format!("{}: {}", interface.name, e));
+                        continue;
+                    }
+                };
+
+            match get_resolved_interface(cached_interface.data, &project_config) {
+                Ok(resolved_interface) => {
+                    report.resolved += 1;
+                    queue_log(
+                        &app_handle,
+                        Some(resolved_interface),
+                        format!("接口 {} 已从缓存生成！", interface.name),
+                        None,
+                        0,
+                    );
+                }
+                Err(e) => report.failed.push(format!("{}: {}", interface.name, e)),
+            }
+        }
+    }
+
+    log(
+        &app_handle,
+        format!(
+            "从缓存生成{}个接口，{}个接口没有缓存，{}个接口失败",
+            report.resolved,
+            report.missing.len(),
+            report.failed.len()
+        ),
+    );
+
+    Ok(WebResponse {
+        message: format!("已从缓存生成{}个接口", report.resolved),
+        data: Some(json!(report)),
+    })
+}
+
+#[tauri::command]
+pub fn get_cache_stats(app_handle: AppHandle, source_path: &str) -> Result<WebResponse, String> {
+    match cache::get_cache_stats(source_path) {
+        Ok(cache_stats) => Ok(WebResponse {
+            message: "获取缓存信息成功".to_string(),
+            data: Some(json!(cache_stats)),
+        }),
+        Err(e) => log_error(&app_handle, e.to_string()),
+    }
+}
+
+// 不传 project_id 时清除全部缓存
+#[tauri::command]
+pub fn invalidate_interface_cache(
+    app_handle: AppHandle,
+    source_path: &str,
+    project_id: Option<String>,
+    interface_id: Option<String>,
+) -> Result<WebResponse, String> {
+    match invalidate_cache(source_path, project_id.as_deref(), interface_id.as_deref()) {
+        Ok(count) => Ok(WebResponse {
+            message: format!("已清除{}个缓存", count),
+            data: Some(json!(count)),
+        }),
+        Err(e) => log_error(&app_handle, e.to_string()),
+    }
+}
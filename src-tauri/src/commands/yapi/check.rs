@@ -0,0 +1,42 @@
+use serde_json::json;
+use tauri::AppHandle;
+
+use crate::{
+    models::web_response::WebResponse,
+    services::{
+        log::{log, log_error},
+        yapi::check::get_check_report,
+    },
+};
+
+// 检查生成的 types 与 request 文件是否为最新，不写入任何文件
+// 存在差异或有接口检查失败时返回错误，错误内容为检查结果的 json
+#[tauri::command]
+pub async fn check_generated_files(
+    app_handle: AppHandle,
+    source_path: &str,
+    use_cache: Option<bool>,
+) -> Result<WebResponse, String> {
+    match get_check_report(source_path, use_cache.unwrap_or(false), &app_handle).await {
+        Err(e) => log_error(&app_handle, e.to_string()),
+        Ok(report) if report.drifts.is_empty() && report.failed.is_empty() => Ok(WebResponse {
+            message: format!(
+                "已检查{}个文件，全部为最新，跳过{}个锁定的接口",
+                report.checked,
+                report.locked.len()
+            ),
+            data: Some(json!(report)),
+        }),
+        Ok(report) => {
+            log(
+                &app_handle,
+                format!(
+                    "检查生成文件：{}个文件不一致，{}个接口检查失败",
+                    report.drifts.len(),
+                    report.failed.len()
+                ),
+            );
+            Err(json!(report).to_string())
+        }
+    }
+}
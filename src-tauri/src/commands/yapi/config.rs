@@ -5,7 +5,7 @@ use crate::{
     services::{
         log::log_error,
         yapi::config::{
-            export_project_config as _export_project_config, get_project_config, init_project_config, merge_config_projects, write_project_config
+            export_project_config as _export_project_config, get_project_config, init_project_config, merge_config_projects, update_lock, write_project_config
         },
     },
 };
@@ -85,4 +85,29 @@ pub fn export_project_config(
         }),
         Err(e) => log_error(&app_handle, e.to_string()),
     }
+}
+
+// 锁定或解锁项目、分类或接口，锁定后的接口不会加入队列，写入文件需强制
+#[tauri::command]
+pub fn update_interface_lock(
+    source_path: &str,
+    project_id: &str,
+    cat_id: Option<String>,
+    interface_id: Option<String>,
+    lock: bool,
+    app_handle: AppHandle,
+) -> Result<WebResponse, String> {
+    match update_lock(
+        source_path,
+        project_id,
+        cat_id.as_deref(),
+        interface_id.as_deref(),
+        lock,
+    ) {
+        Ok(_) => Ok(WebResponse {
+            message: String::from(if lock { "已锁定" } else { "已解锁" }),
+            data: None,
+        }),
+        Err(e) => log_error(&app_handle, e.to_string()),
+    }
 }
\ No newline at end of file
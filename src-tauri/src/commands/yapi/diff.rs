@@ -0,0 +1,44 @@
+use serde_json::json;
+use tauri::AppHandle;
+
+use crate::{
+    models::web_response::WebResponse,
+    services::{
+        log::{log, log_error},
+        yapi::diff::{get_change_report, write_change_report},
+    },
+};
+
+// 写入文件前比较上次生成与最新接口详情的结构变化，报告同时保存为 markdown 与 json
+// 已有报告时只有 overwrite 为 true 才覆盖
+#[tauri::command]
+pub fn get_interface_change_report(
+    app_handle: AppHandle,
+    source_path: &str,
+    project_id: Option<String>,
+    overwrite: Option<bool>,
+) -> Result<WebResponse, String> {
+    match get_change_report(source_path, project_id.as_deref())
+        .and_then(|report| write_change_report(source_path, report, overwrite.unwrap_or(false)))
+    {
+        Ok(report) => {
+            log(
+                &app_handle,
+                format!(
+                    "{}个接口有变化，其中{}个包含破坏性变更",
+                    report.interfaces.len(),
+                    report.breaking
+                ),
+            );
+            Ok(WebResponse {
+                message: format!(
+                    "{}个接口有变化，{}个包含破坏性变更",
+                    report.interfaces.len(),
+                    report.breaking
+                ),
+                data: Some(json!(report)),
+            })
+        }
+        Err(e) => log_error(&app_handle, e.to_string()),
+    }
+}
@@ -0,0 +1,95 @@
+use serde_json::json;
+use tauri::AppHandle;
+
+use crate::{
+    models::web_response::WebResponse,
+    services::{
+        log::log_error,
+        yapi::{
+            config::get_project_config,
+            diff::get_interface_diff,
+            history::{get_history_version, list_history},
+            interface::get_resolved_interface,
+        },
+    },
+};
+
+// 接口同步过的全部版本，按时间从早到晚排列
+#[tauri::command]
+pub fn list_interface_history(
+    app_handle: AppHandle,
+    source_path: &str,
+    project_id: &str,
+    interface_id: &str,
+) -> Result<WebResponse, String> {
+    match list_history(source_path, project_id, interface_id) {
+        Ok(versions) => Ok(WebResponse {
+            message: format!("共{}个版本", versions.len()),
+            data: Some(json!(versions)),
+        }),
+        Err(e) => log_error(&app_handle, e.to_string()),
+    }
+}
+
+// 比较接口的两个版本
+#[tauri::command]
+pub fn diff_interface_history(
+    app_handle: AppHandle,
+    source_path: &str,
+    project_id: &str,
+    interface_id: &str,
+    from_version: &str,
+    to_version: &str,
+) -> Result<WebResponse, String> {
+    let versions = get_history_version(source_path, project_id, interface_id, from_version)
+        .and_then(|from| {
+            get_history_version(source_path, project_id, interface_id, to_version)
+                .map(|to| (from, to))
+        });
+
+    match versions {
+        Ok((from, to)) => Ok(WebResponse {
+            message: "获取成功".to_string(),
+            data: Some(json!(get_interface_diff(
+                project_id,
+                Some(&from.data),
+                &to.data
+            ))),
+        }),
+        Err(e) => log_error(&app_handle, e.to_string()),
+    }
+}
+
+// 用历史版本的接口详情重新生成，返回内容与 get_interface_detail 一致
+#[tauri::command]
+pub fn regenerate_from_history(
+    app_handle: AppHandle,
+    source_path: &str,
+    project_id: &str,
+    interface_id: &str,
+    version: &str,
+) -> Result<WebResponse, String> {
+    let project_config = match get_project_config(source_path) {
+        Ok(project_config) => project_config,
+        Err(e) => return log_error(&app_handle, e.to_string()),
+    };
+
+    match get_history_version(source_path, project_id, interface_id, version) {
+        Err(e) => log_error(&app_handle, e.to_string()),
+        Ok(history_version) => {
+            match get_resolved_interface(history_version.data, &project_config) {
+                Ok(resolved_interface) => Ok(WebResponse {
+                    data: Some(json!({
+                      "interface_data" : resolved_interface.interface,
+                      "ts": resolved_interface.ts_string,
+                      "schema": resolved_interface.schema_string,
+                      "dart": resolved_interface.dart_string,
+                      "go": resolved_interface.go_string
+                    })),
+                    message: "获取成功".to_string(),
+                }),
+                Err(e) => log_error(&app_handle, e.to_string()),
+            }
+        }
+    }
+}
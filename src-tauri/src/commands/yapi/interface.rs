@@ -1,24 +1,57 @@
+use std::{io, path::PathBuf};
+
 use serde_json::json;
 use tauri::{async_runtime::spawn, AppHandle, Manager, State};
 
 use crate::{
     models::{
         web_response::WebResponse,
-        yapi::{interface::InterfaceFetchParams, queue::Queue},
+        yapi::{
+            interface::{InterfaceData, InterfaceFetchParams},
+            queue::Queue,
+        },
     },
     services::{
-        log::log_error,
-        yapi::interface::{
-            fetch_interface_detail, get_interface_ts_string, write_content_to_interface_path,
+        log::{log, log_error},
+        source::fetch_interface_detail,
+        yapi::{
+            cache::mark_cache_generated,
+            config::{
+                check_interface_lock, get_interface_lock_reason, get_project_config,
+                update_generated_info,
+            },
+            diff::{get_write_diff, merge_interface_diff_to_change_report},
+            interface::{
+                get_dart_file_path, get_go_file_path, get_resolved_interface, get_schema_file_path,
+                get_ts_file_path, write_generated_files,
+            },
         },
     },
 };
 
+// 锁定的接口不加入队列
 #[tauri::command]
 pub async fn add_interface_task(
     app_handle: AppHandle,
     data: InterfaceFetchParams,
 ) -> Result<WebResponse, String> {
+    let project_config = match get_project_config(&data.source_path) {
+        Ok(project_config) => project_config,
+        Err(e) => return log_error(&app_handle, e.to_string()),
+    };
+    let interface_id = data.interface_id.to_string();
+
+    if let Some(reason) = get_interface_lock_reason(&project_config, Some(&interface_id), None) {
+        log(
+            &app_handle,
+            format!("跳过接口 {}：{}", interface_id, reason),
+        );
+        return Ok(WebResponse {
+            data: None,
+            message: format!("{}，已跳过", reason),
+        });
+    }
+
     let queue: State<'_, Queue> = app_handle.state();
 
     queue.add_task(data).await;
@@ -55,20 +88,137 @@ pub async fn cancel_task(app_handle: AppHandle) -> Result<WebResponse, String> {
     })
 }
 
+// interface_id 与 up_time 用于记录生成时接口的更新时间
+// interface_data 为生成内容所用的接口详情，写入前与上次生成时比较并记入变更报告
+// 锁定的接口与按配置拒绝覆盖的手动修改文件，只有 force 为 true 时才会写入
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub fn write_to_file(
     path: String,
     content: String,
+    schema_content: Option<String>,
+    dart_content: Option<String>,
+    go_content: Option<String>,
+    interface_id: Option<String>,
+    up_time: Option<u64>,
+    interface_data: Option<InterfaceData>,
+    force: Option<bool>,
     source_path: &str,
     app_handle: AppHandle,
 ) -> Result<WebResponse, String> {
-    match write_content_to_interface_path(path, source_path, content) {
+    let force = force.unwrap_or(false);
+    // 配置只读取一次，供锁定检查、手动修改检查、变更比较与记录生成信息使用
+    let project_config = match get_project_config(source_path) {
+        Ok(project_config) => project_config,
+        Err(e) => return log_error(&app_handle, e.to_string()),
+    };
+    if !force {
+        if let Err(e) = check_interface_lock(&project_config, interface_id.as_deref(), Some(&path))
+        {
+            return log_error(&app_handle, e.to_string());
+        }
+    }
+
+    let files = match get_write_files(
+        &path,
+        source_path,
+        content,
+        schema_content,
+        dart_content,
+        go_content,
+    ) {
+        Ok(files) => files,
+        Err(e) => return log_error(&app_handle, e.to_string()),
+    };
+    let interface_diff = interface_data
+        .as_ref()
+        .and_then(|interface_data| get_write_diff(source_path, &project_config, interface_data));
+    // 覆盖手动修改过的文件时的提示
+    let policy = project_config.manual_edit.clone().unwrap_or_default();
+    let warnings = match write_generated_files(files, &policy, force) {
+        Ok(warnings) => warnings,
+        Err(e) => return log_error(&app_handle, e.to_string()),
+    };
+
+    for warning in &warnings {
+        log(&app_handle, warning.clone());
+    }
+
+    let recorded = match interface_id {
+        Some(interface_id) => {
+            update_generated_info(source_path, project_config, &interface_id, up_time, &path)
+        }
+        None => Ok(()),
+    }
+    // 没有接口详情时无法确定写入的内容，不更新比较基准
+    .and_then(|_| match &interface_data {
+        Some(interface_data) => mark_cache_generated(source_path, interface_data),
+        None => Ok(()),
+    })
+    .and_then(|_| match interface_diff {
+        Some(interface_diff) => {
+            if !interface_diff.is_new {
+                log(
+                    &app_handle,
+                    format!(
+                        "接口 {} 有{}处变化{}",
+                        interface_diff.title,
+                        interface_diff.changes.len(),
+                        if interface_diff.breaking {
+                            "，包含破坏性变更"
+                        } else {
+                            ""
+                        }
+                    ),
+                );
+            }
+            merge_interface_diff_to_change_report(source_path, interface_diff).map(|_| ())
+        }
+        None => Ok(()),
+    });
+
+    match recorded {
         Err(e) => log_error(&app_handle, e.to_string()),
-        Ok(_) => Ok(WebResponse {
+        Ok(_) if warnings.is_empty() => Ok(WebResponse {
             data: None,
             message: "已写入文件".to_string(),
         }),
+        Ok(_) => Ok(WebResponse {
+            message: format!("已写入文件，{}", warnings.join("；")),
+            data: Some(json!(warnings)),
+        }),
+    }
+}
+
+// 一次写入的全部文件与内容
+fn get_write_files(
+    path: &str,
+    source_path: &str,
+    content: String,
+    schema_content: Option<String>,
+    dart_content: Option<String>,
+    go_content: Option<String>,
+) -> Result<Vec<(PathBuf, String)>, io::Error> {
+    let mut files = vec![(get_ts_file_path(path.to_string(), source_path)?, content)];
+
+    if let Some(schema_content) = schema_content {
+        files.push((
+            get_schema_file_path(path.to_string(), source_path)?,
+            schema_content,
+        ));
+    }
+    if let Some(dart_content) = dart_content {
+        files.push((
+            get_dart_file_path(path.to_string(), source_path)?,
+            dart_content,
+        ));
     }
+    if let Some(go_content) = go_content {
+        let go_file_path = get_go_file_path(path.to_string(), source_path, &go_content)?;
+        files.push((go_file_path, go_content));
+    }
+
+    Ok(files)
 }
 
 #[tauri::command]
@@ -76,13 +226,21 @@ pub async fn get_interface_detail(
     app_handle: AppHandle,
     data: InterfaceFetchParams,
 ) -> Result<WebResponse, String> {
+    let project_config = match get_project_config(&data.source_path) {
+        Ok(project_config) => project_config,
+        Err(e) => return log_error(&app_handle, e.to_string()),
+    };
+
     match fetch_interface_detail(data, &app_handle).await {
         Err(e) => log_error(&app_handle, e.to_string()),
-        Ok(res) => match get_interface_ts_string(&res) {
-            Ok(ts) => Ok(WebResponse {
+        Ok(res) => match get_resolved_interface(res.value, &project_config) {
+            Ok(resolved_interface) => Ok(WebResponse {
                 data: Some(json!({
-                  "interface_data" : res,
-                  "ts": ts
+                  "interface_data" : resolved_interface.interface,
+                  "ts": resolved_interface.ts_string,
+                  "schema": resolved_interface.schema_string,
+                  "dart": resolved_interface.dart_string,
+                  "go": resolved_interface.go_string
                 })),
                 message: "获取成功".to_string(),
             }),
@@ -1,5 +1,9 @@
+pub mod cache;
 pub mod category;
+pub mod check;
 pub mod config;
+pub mod diff;
+pub mod history;
 pub mod interface;
 pub mod project;
 pub mod request;
\ No newline at end of file
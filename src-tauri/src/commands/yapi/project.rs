@@ -1,33 +1,83 @@
+use std::io;
+
 use serde_json::json;
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager, State};
 
 use crate::{
-    models::web_response::WebResponse,
+    models::{
+        web_response::WebResponse,
+        yapi::{
+            config::{ProjectSource, YapiServer},
+            interface::InterfaceFetchParams,
+            project::{InterfaceMove, InterfaceSyncReport, OrphanAction},
+            queue::Queue,
+        },
+    },
     services::{
         log::{log, log_error},
         yapi::{
-            config::{merge_category_to_project_config, merge_yapi_project_to_project_config},
-            project::{fetch_project_base_info, fetch_project_cat_menu},
+            config::{
+                get_interface_lock_reason, get_project_config, get_remote_interface_report,
+                is_interface_stale, merge_added_interfaces_to_project_config,
+                merge_category_to_project_config, merge_project_menu_to_project_config,
+                merge_yapi_project_to_project_config, update_project_server,
+            },
+            orphan::{relocate_moved_interfaces, resolve_orphaned_interfaces},
+            project::{fetch_project_base_info, fetch_project_cat_menu, fetch_project_menu},
+            rename::relocate_renamed_interfaces,
         },
     },
 };
 
+// base_url 与 proxy 用于添加其他 yapi 上的项目，为空时使用代码库的默认配置
 #[tauri::command]
 pub async fn get_yapi_project_base_info(
     app_handle: AppHandle,
     token: &str,
     source_path: &str,
+    base_url: Option<String>,
+    proxy: Option<String>,
 ) -> Result<WebResponse, String> {
-    match fetch_project_base_info(token.to_string(), source_path, &app_handle).await {
+    let server = base_url
+        .clone()
+        .filter(|base_url| !base_url.is_empty())
+        .map(|mut base_url| {
+            if !base_url.ends_with('/') {
+                base_url.push('/');
+            }
+            YapiServer {
+                base_url,
+                proxy: proxy.clone().filter(|proxy| !proxy.is_empty()),
+            }
+        });
+
+    match fetch_project_base_info(token.to_string(), source_path, server, &app_handle).await {
         Ok(yapi_project_base_info) => {
-            match merge_yapi_project_to_project_config(source_path, &yapi_project_base_info, token)
-            {
+            match merge_yapi_project_to_project_config(
+                source_path,
+                &yapi_project_base_info,
+                token,
+                None,
+                base_url.as_deref(),
+            )
+            .and_then(|_| {
+                update_project_server(
+                    source_path,
+                    &yapi_project_base_info._id.to_string(),
+                    base_url,
+                    proxy,
+                )
+            }) {
                 Ok(_) => {
                     log(
                         &app_handle,
                         format!("更新项目{}至配置文件成功", yapi_project_base_info.name),
                     );
                 }
+                // 不同 yapi 上 id 相同的项目无法添加
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                    return log_error(&app_handle, e.to_string());
+                }
                 Err(e) => {
                     log(&app_handle, e.to_string());
                 }
@@ -75,3 +125,325 @@ pub async fn get_yapi_project_cat_menu(
         Err(err) => log_error(&app_handle, err.to_string()),
     }
 }
+
+// 通过 list_menu 一次性同步项目下的全部分类与接口，返回新增、改名与删除的接口
+#[tauri::command]
+pub async fn discover_yapi_project(
+    app_handle: AppHandle,
+    token: &str,
+    project_id: u32,
+    source_path: &str,
+) -> Result<WebResponse, String> {
+    let report = fetch_project_menu(project_id, token.to_string(), source_path, &app_handle)
+        .await
+        .and_then(|menu| {
+            merge_project_menu_to_project_config(source_path, &project_id.to_string(), &menu)
+        });
+
+    match report {
+        Ok(report) => {
+            log(
+                &app_handle,
+                format!(
+                    "项目{}同步完成：新增{}个接口，改名{}个接口，删除{}个接口",
+                    project_id,
+                    report.added.len(),
+                    report.renamed.len(),
+                    report.removed.len()
+                ),
+            );
+            Ok(WebResponse {
+                message: "同步yapi项目成功".to_string(),
+                data: Some(json!(report)),
+            })
+        }
+        Err(err) => log_error(&app_handle, err.to_string()),
+    }
+}
+
+// 增量同步：先刷新项目目录中的 up_time，只把有更新的接口加入队列
+// 加入队列后需调用 start_task 开始执行
+#[tauri::command]
+pub async fn sync_yapi_project(
+    app_handle: AppHandle,
+    token: &str,
+    project_id: u32,
+    source_path: &str,
+) -> Result<WebResponse, String> {
+    let project_config = match get_project_config(source_path) {
+        Ok(project_config) => project_config,
+        Err(e) => return log_error(&app_handle, e.to_string()),
+    };
+    let is_yapi_source = project_config
+        .project_list
+        .iter()
+        .find(|project| project.project_id == project_id.to_string())
+        .map_or(true, |project| {
+            matches!(project.source, None | Some(ProjectSource::Yapi))
+        });
+
+    // 文件导入的项目没有 up_time，无需刷新目录
+    let discovery = if is_yapi_source {
+        let report = fetch_project_menu(project_id, token.to_string(), source_path, &app_handle)
+            .await
+            .and_then(|menu| {
+                merge_project_menu_to_project_config(source_path, &project_id.to_string(), &menu)
+            });
+        match report {
+            Ok(report) => Some(report),
+            Err(e) => return log_error(&app_handle, e.to_string()),
+        }
+    } else {
+        None
+    };
+
+    let project_config = match get_project_config(source_path) {
+        Ok(project_config) => project_config,
+        Err(e) => return log_error(&app_handle, e.to_string()),
+    };
+    let interfaces: Vec<_> = project_config
+        .project_list
+        .iter()
+        .filter(|project| project.project_id == project_id.to_string())
+        .flat_map(|project| &project.categories)
+        .flat_map(|category| &category.interfaces)
+        .collect();
+
+    let queue: State<'_, Queue> = app_handle.state();
+    let mut report = InterfaceSyncReport {
+        project_id: project_id.to_string(),
+        enqueued: 0,
+        skipped: 0,
+        locked: 0,
+        discovery,
+    };
+
+    for interface in interfaces {
+        let interface_id = match interface.id.parse::<u32>() {
+            Ok(interface_id) => interface_id,
+            Err(_) => continue,
+        };
+        if let Some(reason) = get_interface_lock_reason(&project_config, Some(&interface.id), None)
+        {
+            log(
+                &app_handle,
+                format!("跳过接口 {}：{}", interface.name, reason),
+            );
+            report.locked += 1;
+            continue;
+        }
+        // yapi 中已删除的接口无法获取
+        if interface.orphaned == Some(true) || !is_interface_stale(interface) {
+            report.skipped += 1;
+            continue;
+        }
+
+        queue
+            .add_task(InterfaceFetchParams {
+                interface_id,
+                token: token.to_string(),
+                source_path: source_path.to_string(),
+            })
+            .await;
+        report.enqueued += 1;
+    }
+
+    log(
+        &app_handle,
+        format!(
+            "项目{}增量同步：{}个接口加入队列，{}个接口未变更已跳过，{}个接口已锁定",
+            project_id, report.enqueued, report.skipped, report.locked
+        ),
+    );
+
+    Ok(WebResponse {
+        message: format!(
+            "{}个接口待更新，{}个接口未变更，{}个接口已锁定",
+            report.enqueued, report.skipped, report.locked
+        ),
+        data: Some(json!(report)),
+    })
+}
+
+// 对比 yapi 项目与本地记录，返回新增、有更新与从未生成的接口
+// enqueue_new 为 true 时先只把新增接口记录到 yapi.json，再全部加入队列，之后需调用 start_task 开始执行
+#[tauri::command]
+pub async fn discover_remote_yapi_interfaces(
+    app_handle: AppHandle,
+    token: &str,
+    project_id: u32,
+    source_path: &str,
+    enqueue_new: Option<bool>,
+) -> Result<WebResponse, String> {
+    let menu =
+        match fetch_project_menu(project_id, token.to_string(), source_path, &app_handle).await {
+            Ok(menu) => menu,
+            Err(e) => return log_error(&app_handle, e.to_string()),
+        };
+    let mut report = match get_project_config(source_path).and_then(|project_config| {
+        get_remote_interface_report(&project_config, &project_id.to_string(), &menu)
+    }) {
+        Ok(report) => report,
+        Err(e) => return log_error(&app_handle, e.to_string()),
+    };
+
+    if enqueue_new.unwrap_or(false) && !report.added.is_empty() {
+        // 生成后需要在 yapi.json 中记录生成信息，新增接口要先记录下来
+        // 只记录新增接口，改名、删除等变化仍由同步处理
+        let project_config = match merge_added_interfaces_to_project_config(
+            source_path,
+            &project_id.to_string(),
+            &menu,
+            &report.added,
+        )
+        .and_then(|_| get_project_config(source_path))
+        {
+            Ok(project_config) => project_config,
+            Err(e) => return log_error(&app_handle, e.to_string()),
+        };
+        let queue: State<'_, Queue> = app_handle.state();
+
+        for interface in &report.added {
+            let interface_id = match interface.id.parse::<u32>() {
+                Ok(interface_id) => interface_id,
+                Err(_) => continue,
+            };
+            if let Some(reason) =
+                get_interface_lock_reason(&project_config, Some(&interface.id), None)
+            {
+                log(
+                    &app_handle,
+                    format!("跳过接口 {}：{}", interface.name, reason),
+                );
+                continue;
+            }
+
+            queue
+                .add_task(InterfaceFetchParams {
+                    interface_id,
+                    token: token.to_string(),
+                    source_path: source_path.to_string(),
+                })
+                .await;
+            report.enqueued += 1;
+        }
+    }
+
+    log(
+        &app_handle,
+        format!(
+            "项目{}：新增{}个接口，更新{}个接口，{}个接口从未生成",
+            project_id,
+            report.added.len(),
+            report.changed.len(),
+            report.ungenerated.len()
+        ),
+    );
+
+    Ok(WebResponse {
+        message: format!(
+            "新增{}个接口，更新{}个接口，{}个接口从未生成",
+            report.added.len(),
+            report.changed.len(),
+            report.ungenerated.len()
+        ),
+        data: Some(json!(report)),
+    })
+}
+
+// 设置项目的 yapi 地址与代理，传入空字符串时恢复为代码库的默认配置
+#[tauri::command]
+pub fn update_yapi_project_server(
+    app_handle: AppHandle,
+    source_path: &str,
+    project_id: &str,
+    base_url: Option<String>,
+    proxy: Option<String>,
+) -> Result<WebResponse, String> {
+    match update_project_server(source_path, project_id, base_url, proxy) {
+        Ok(_) => Ok(WebResponse {
+            message: String::from("更新成功！"),
+            data: None,
+        }),
+        Err(e) => log_error(&app_handle, e.to_string()),
+    }
+}
+
+// 处理 yapi 中已删除的接口：删除生成的文件，或把其中的类型标记为废弃
+// 锁定的接口与手动修改过的文件会被跳过，force 为 true 时强制处理
+#[tauri::command]
+pub fn resolve_orphaned_yapi_interfaces(
+    app_handle: AppHandle,
+    source_path: &str,
+    project_id: &str,
+    action: OrphanAction,
+    force: Option<bool>,
+) -> Result<WebResponse, String> {
+    match resolve_orphaned_interfaces(source_path, project_id, &action, force.unwrap_or(false)) {
+        Ok(report) => {
+            log(
+                &app_handle,
+                format!("处理孤立接口：{}个文件", report.files.len()),
+            );
+            Ok(WebResponse {
+                message: format!("已处理{}个文件", report.files.len()),
+                data: Some(json!(report)),
+            })
+        }
+        Err(e) => log_error(&app_handle, e.to_string()),
+    }
+}
+
+// 把同步项目时发现的移动接口已生成的文件移到新位置
+// 锁定的接口与手动修改过的文件会被跳过，force 为 true 时强制移动
+#[tauri::command]
+pub fn relocate_moved_yapi_interfaces(
+    app_handle: AppHandle,
+    source_path: &str,
+    project_id: &str,
+    moves: Vec<InterfaceMove>,
+    force: Option<bool>,
+) -> Result<WebResponse, String> {
+    match relocate_moved_interfaces(source_path, project_id, &moves, force.unwrap_or(false)) {
+        Ok(report) => {
+            log(
+                &app_handle,
+                format!("移动接口文件：{}个文件", report.files.len()),
+            );
+            Ok(WebResponse {
+                message: format!("已移动{}个文件", report.files.len()),
+                data: Some(json!(report)),
+            })
+        }
+        Err(e) => log_error(&app_handle, e.to_string()),
+    }
+}
+
+// 按接口 id 找出 yapi 中改变了路径的接口，移动已生成的文件并更新请求文件中的 import
+// 锁定的接口会被跳过，force 为 true 时强制处理
+#[tauri::command]
+pub fn relocate_renamed_yapi_interfaces(
+    app_handle: AppHandle,
+    source_path: &str,
+    project_id: Option<String>,
+    force: Option<bool>,
+) -> Result<WebResponse, String> {
+    match relocate_renamed_interfaces(source_path, project_id.as_deref(), force.unwrap_or(false)) {
+        Ok(report) => {
+            log(
+                &app_handle,
+                format!(
+                    "{}个接口路径变化，移动{}个文件，更新{}个请求文件",
+                    report.renames.len(),
+                    report.moved.len(),
+                    report.rewritten.len()
+                ),
+            );
+            Ok(WebResponse {
+                message: format!("{}个接口路径变化", report.renames.len()),
+                data: Some(json!(report)),
+            })
+        }
+        Err(e) => log_error(&app_handle, e.to_string()),
+    }
+}
@@ -2,19 +2,36 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use models::yapi::queue::Queue;
-use services::{conversion::path_buf_to_string, global_config::init_config};
+use services::{
+    conversion::path_buf_to_string, global_config::init_config, reqwest::HttpClientState,
+    source::SourceProjectState,
+};
 use tauri::{api::dialog, CustomMenuItem, Manager, Menu, MenuItem, Submenu};
 
 use crate::commands::{
     global_config::{update_project, load_global_config, load_latest_project, update_global_config},
+    openapi::export_openapi,
+    source::{
+        import_har_source, import_openapi_source, import_postman_source, import_yapi_dump_source,
+    },
+    yapi::cache::{get_cache_stats, invalidate_interface_cache, regenerate_from_cache},
     yapi::category::get_cat_interface_list,
+    yapi::check::check_generated_files,
     yapi::config::{
-        export_project_config, load_project_config, merge_project_config, update_project_config,
+        export_project_config, load_project_config, merge_project_config, update_interface_lock,
+        update_project_config,
     },
+    yapi::diff::get_interface_change_report,
+    yapi::history::{diff_interface_history, list_interface_history, regenerate_from_history},
     yapi::interface::{
         add_interface_task, cancel_task, get_interface_detail, start_task, write_to_file,
     },
-    yapi::project::{get_yapi_project_base_info, get_yapi_project_cat_menu},
+    yapi::project::{
+        discover_remote_yapi_interfaces, discover_yapi_project, get_yapi_project_base_info,
+        get_yapi_project_cat_menu, relocate_moved_yapi_interfaces,
+        relocate_renamed_yapi_interfaces, resolve_orphaned_yapi_interfaces, sync_yapi_project,
+        update_yapi_project_server,
+    },
     yapi::request::{get_request_string, load_file_tree, write_request_to_file},
 };
 
@@ -44,6 +61,8 @@ fn main() {
             init_config(&app_handle).unwrap();
 
             app.manage(Queue::new(&app_handle));
+            app.manage(HttpClientState::new(&app_handle));
+            app.manage(SourceProjectState::default());
             Ok(())
         })
         .menu(menu)
@@ -89,7 +108,28 @@ fn main() {
             write_request_to_file,
             get_interface_detail,
             merge_project_config,
-            export_project_config
+            export_project_config,
+            export_openapi,
+            import_openapi_source,
+            import_yapi_dump_source,
+            import_har_source,
+            import_postman_source,
+            update_yapi_project_server,
+            discover_yapi_project,
+            sync_yapi_project,
+            regenerate_from_cache,
+            get_cache_stats,
+            invalidate_interface_cache,
+            update_interface_lock,
+            get_interface_change_report,
+            resolve_orphaned_yapi_interfaces,
+            relocate_moved_yapi_interfaces,
+            relocate_renamed_yapi_interfaces,
+            discover_remote_yapi_interfaces,
+            list_interface_history,
+            diff_interface_history,
+            regenerate_from_history,
+            check_generated_files
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
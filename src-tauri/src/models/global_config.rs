@@ -1,6 +1,8 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct GlobalConfig {
     // 项目工程
     pub projects: Vec<String>,
@@ -10,6 +12,44 @@ pub struct GlobalConfig {
     pub rate_limit: usize,
     // 代理
     pub proxy: Option<String>,
+    // yapi 请求的重试策略
+    pub retry: Option<RetryPolicy>,
+    // 请求超时（秒）
+    pub timeout_seconds: Option<u64>,
+    // 连接超时（秒）
+    pub connect_timeout_seconds: Option<u64>,
+    // 附加的请求头，如 SSO 的 Cookie
+    pub headers: Option<BTreeMap<String, String>>,
+    // 额外信任的 CA 证书文件（PEM，可包含多个证书）
+    pub ca_cert_path: Option<String>,
+    // 信任自签名等无效证书
+    pub accept_invalid_certs: Option<bool>,
+    // 分页获取分类接口列表时的每页数量
+    pub page_size: Option<u32>,
+}
+
+// 仅在连接失败、超时、5xx 与 429 时重试
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RetryPolicy {
+    // 最大尝试次数，包含第一次请求
+    pub max_attempts: u32,
+    // 首次重试前的等待时间，之后每次翻倍
+    pub base_delay_ms: u64,
+    // 等待时间上限
+    pub max_delay_ms: u64,
+    // 随机增加的等待时间上限，避免并行的请求同时重试
+    pub jitter_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay_ms: 500,
+            max_delay_ms: 10_000,
+            jitter_ms: 300,
+        }
+    }
 }
 
 impl Default for GlobalConfig {
@@ -19,6 +59,13 @@ impl Default for GlobalConfig {
             break_seconds: 3,
             rate_limit: 5,
             proxy: None,
+            retry: Some(RetryPolicy::default()),
+            timeout_seconds: Some(30),
+            connect_timeout_seconds: Some(10),
+            headers: None,
+            ca_cert_path: None,
+            accept_invalid_certs: None,
+            page_size: Some(100),
         }
     }
 }
@@ -34,6 +81,27 @@ impl GlobalConfig {
         if let Some(proxy) = update.proxy {
             self.proxy = Some(proxy);
         }
+        if let Some(retry) = update.retry {
+            self.retry = Some(retry);
+        }
+        if let Some(timeout_seconds) = update.timeout_seconds {
+            self.timeout_seconds = Some(timeout_seconds);
+        }
+        if let Some(connect_timeout_seconds) = update.connect_timeout_seconds {
+            self.connect_timeout_seconds = Some(connect_timeout_seconds);
+        }
+        if let Some(headers) = update.headers {
+            self.headers = Some(headers);
+        }
+        if let Some(ca_cert_path) = update.ca_cert_path {
+            self.ca_cert_path = Some(ca_cert_path);
+        }
+        if let Some(accept_invalid_certs) = update.accept_invalid_certs {
+            self.accept_invalid_certs = Some(accept_invalid_certs);
+        }
+        if let Some(page_size) = update.page_size {
+            self.page_size = Some(page_size);
+        }
     }
 }
 
@@ -42,4 +110,23 @@ pub struct GlobalConfigRequest {
     pub rate_limit: Option<usize>,
     pub break_seconds: Option<u64>,
     pub proxy: Option<String>,
+    pub retry: Option<RetryPolicy>,
+    pub timeout_seconds: Option<u64>,
+    pub connect_timeout_seconds: Option<u64>,
+    pub headers: Option<BTreeMap<String, String>>,
+    pub ca_cert_path: Option<String>,
+    pub accept_invalid_certs: Option<bool>,
+    pub page_size: Option<u32>,
+}
+
+impl GlobalConfigRequest {
+    // 是否修改了网络配置，修改后需要重建 http 客户端
+    pub fn is_network_changed(&self) -> bool {
+        self.proxy.is_some()
+            || self.timeout_seconds.is_some()
+            || self.connect_timeout_seconds.is_some()
+            || self.headers.is_some()
+            || self.ca_cert_path.is_some()
+            || self.accept_invalid_certs.is_some()
+    }
 }
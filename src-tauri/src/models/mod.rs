@@ -2,4 +2,6 @@ pub mod web_response;
 pub mod global_config;
 pub mod yapi;
 pub mod notification;
-pub mod file;
\ No newline at end of file
+pub mod file;
+pub mod openapi;
+pub mod source;
\ No newline at end of file
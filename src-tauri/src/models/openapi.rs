@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+// 导出的 OpenAPI 文档格式
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenApiFormat {
+    Json,
+    Yaml,
+}
+
+// 导出结果
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct OpenApiExportReport {
+    pub file_path: String,
+    pub total: usize,
+    pub exported: usize,
+    pub failed: Vec<String>,
+}
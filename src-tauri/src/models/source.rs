@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+use super::yapi::interface::InterfaceData;
+
+// 从非 yapi 来源解析出的项目，结构与 yapi 的项目/分类/接口保持一致
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SourceProject {
+    pub project_id: u32,
+    pub name: String,
+    pub categories: Vec<SourceCategory>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SourceCategory {
+    pub cat_id: u32,
+    pub name: String,
+    pub interfaces: Vec<InterfaceData>,
+}
+
+// 导入结果
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SourceImportReport {
+    pub project_id: String,
+    pub project_name: String,
+    pub categories: usize,
+    pub interfaces: usize,
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use super::interface::InterfaceData;
+
+// 缓存的接口详情，up_time 与 yapi.json 中记录的不一致时视为过期
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CachedInterface {
+    pub up_time: Option<u64>,
+    // 写入缓存的时间戳，单位秒
+    pub cached_at: u64,
+    pub data: InterfaceData,
+    // 生成当前文件时的接口详情，用于比较变更
+    pub generated: Option<InterfaceData>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct CacheStats {
+    pub cache_dir: String,
+    // 缓存文件数
+    pub entries: usize,
+    // 缓存文件总大小，单位字节
+    pub bytes: u64,
+    // up_time 落后于 yapi.json 的缓存数
+    pub stale: usize,
+    // yapi.json 中存在但没有缓存的接口数
+    pub missing: usize,
+}
+
+// 从缓存重新生成的结果
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct CacheRegenerateReport {
+    pub total: usize,
+    pub resolved: usize,
+    // 没有缓存的接口
+    pub missing: Vec<String>,
+    // 转换失败的接口
+    pub failed: Vec<String>,
+}
@@ -24,4 +24,26 @@ pub struct InterfaceDataItem {
     pub catid : u32,
     pub title : String,
     pub path : String,
+    pub up_time : Option<u64>,
 }
+
+// 分页获取分类接口列表时每页的进度
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CategoryFetchProgress {
+    pub cat_id: u32,
+    // 当前页，从 1 开始
+    pub page: u32,
+    pub total_pages: u32,
+    // 已获取的接口数
+    pub fetched: u32,
+    // 接口总数
+    pub count: u32,
+}
+
+// list_menu 返回的分类及其下的接口
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct MenuCategory {
+    pub _id: u32,
+    pub name: String,
+    pub list: Vec<InterfaceDataItem>,
+}
\ No newline at end of file
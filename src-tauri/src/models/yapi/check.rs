@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+// 生成文件与磁盘上文件的差异类型
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FileDriftKind {
+    // 文件内容与重新生成的结果不一致
+    Stale,
+    // 应生成但磁盘上不存在
+    Missing,
+    // 磁盘上带有来源信息，但不再对应任何接口
+    Extra,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct FileDrift {
+    pub file_path: String,
+    pub kind: FileDriftKind,
+    // 磁盘内容到重新生成内容的差异，仅 stale 时存在
+    pub diff: Option<String>,
+}
+
+// 检查生成文件的结果
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct CheckReport {
+    // 比较过的文件数
+    pub checked: usize,
+    pub drifts: Vec<FileDrift>,
+    // 获取或生成失败的接口及原因
+    pub failed: Vec<String>,
+    // 锁定而跳过检查的接口及原因
+    pub locked: Vec<String>,
+}
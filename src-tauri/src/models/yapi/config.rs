@@ -11,6 +11,11 @@ pub struct YapiConfig {
     pub header_template: String,
     pub file_name_template: String,
     pub type_import_template: String,
+    pub key_case: Option<KeyCase>,
+    pub zod_mode: Option<ZodMode>,
+    pub dart_path: Option<String>,
+    pub go_path: Option<String>,
+    pub manual_edit: Option<ManualEditPolicy>,
 }
 
 impl Default for YapiConfig {
@@ -24,6 +29,11 @@ impl Default for YapiConfig {
             header_template: String::new(),
             file_name_template: String::new(),
             type_import_template: String::new(),
+            key_case: None,
+            zod_mode: None,
+            dart_path: None,
+            go_path: None,
+            manual_edit: None,
         }
     }
 }
@@ -61,6 +71,21 @@ impl YapiConfig {
         if let Some(type_import_template) = yapi_config_request.type_import_template {
             self.type_import_template = type_import_template;
         }
+        if let Some(key_case) = yapi_config_request.key_case {
+            self.key_case = Some(key_case);
+        }
+        if let Some(zod_mode) = yapi_config_request.zod_mode {
+            self.zod_mode = Some(zod_mode);
+        }
+        if let Some(dart_path) = yapi_config_request.dart_path {
+            self.dart_path = Some(dart_path);
+        }
+        if let Some(go_path) = yapi_config_request.go_path {
+            self.go_path = Some(go_path);
+        }
+        if let Some(manual_edit) = yapi_config_request.manual_edit {
+            self.manual_edit = Some(manual_edit);
+        }
     }
 }
 
@@ -73,6 +98,47 @@ pub struct YapiConfigRequest {
     pub header_template: Option<String>,
     pub file_name_template: Option<String>,
     pub type_import_template: Option<String>,
+    pub key_case: Option<KeyCase>,
+    pub zod_mode: Option<ZodMode>,
+    pub dart_path: Option<String>,
+    pub go_path: Option<String>,
+    pub manual_edit: Option<ManualEditPolicy>,
+}
+
+// 生成 ts 类型时属性名的风格
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyCase {
+    // 保持接口字段名
+    #[default]
+    Original,
+    // 小驼峰
+    Camel,
+    // 下划线
+    Snake,
+}
+
+// 覆盖生成后被手动修改过的文件时的处理方式
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ManualEditPolicy {
+    // 照常覆盖并提示
+    #[default]
+    Warn,
+    // 拒绝覆盖，需强制写入
+    Refuse,
+    // 直接覆盖
+    Overwrite,
+}
+
+// zod schema 的输出方式，未配置时不生成
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ZodMode {
+    // 与 interface 写入同一个文件
+    Inline,
+    // 写入同目录下的 .schema.ts 文件
+    Sibling,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -81,6 +147,38 @@ pub struct YapiProject {
     pub project_id: String,
     pub project_name: Option<String>,
     pub categories: Vec<YapiCategory>,
+    // 接口来源，未配置时为 yapi 项目
+    pub source: Option<ProjectSource>,
+    // 项目所在的 yapi 地址，未配置时使用代码库的 base_url
+    pub base_url: Option<String>,
+    // 访问该 yapi 使用的代理，未配置时使用全局代理
+    pub proxy: Option<String>,
+    // 锁定后项目下的接口都不会被重新生成
+    pub lock: Option<bool>,
+}
+
+// 请求 yapi 时使用的地址与代理
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct YapiServer {
+    // 以 / 结尾
+    pub base_url: String,
+    pub proxy: Option<String>,
+}
+
+// 项目的接口来源
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProjectSource {
+    // yapi 项目，通过 token + project_id 获取
+    Yapi,
+    // 本地 OpenAPI 2/3 文件，相对路径基于代码库根目录
+    OpenApi { file_path: String },
+    // yapi 导出的 json 文件，无需访问 yapi
+    YapiDump { file_path: String },
+    // 浏览器导出的 har 文件，由请求记录推断接口
+    Har { file_path: String },
+    // Postman v2.1 collection，示例响应用于推断响应类型
+    Postman { file_path: String },
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -88,6 +186,8 @@ pub struct YapiCategory {
     pub id: String,
     pub name: String,
     pub interfaces: Vec<YapiInterface>,
+    // 锁定后分类下的接口都不会被重新生成
+    pub lock: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -95,5 +195,13 @@ pub struct YapiInterface {
     pub id: String,
     pub name: String,
     pub path: Option<String>,
-    pub lock: Option<bool>
+    pub lock: Option<bool>,
+    // yapi 中接口的更新时间
+    pub up_time: Option<u64>,
+    // 上次生成文件时接口的更新时间
+    pub generated_up_time: Option<u64>,
+    // 上次生成文件时接口的路径
+    pub generated_path: Option<String>,
+    // yapi 中已删除，本地仍保留的接口
+    pub orphaned: Option<bool>,
 }
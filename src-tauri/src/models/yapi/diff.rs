@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+// 字段的变更类型
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldChangeKind {
+    Added,
+    Removed,
+    RequiredToOptional,
+    OptionalToRequired,
+    TypeChanged,
+    // 嵌套对象生成的 interface 名称变化
+    InterfaceRenamed,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct FieldChange {
+    // request 或 response
+    pub web_type: String,
+    // 字段路径，数组元素以 [] 表示，如 data.list[].name
+    pub field: String,
+    pub kind: FieldChangeKind,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    // 是否会导致使用方编译失败或运行出错
+    pub breaking: bool,
+}
+
+// 单个接口上次生成与本次之间的变化
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct InterfaceDiff {
+    pub project_id: String,
+    pub interface_id: String,
+    pub title: String,
+    pub method: String,
+    pub path: String,
+    // 从未生成过的接口
+    pub is_new: bool,
+    pub breaking: bool,
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ChangeReport {
+    pub interfaces: Vec<InterfaceDiff>,
+    // 包含破坏性变更的接口数
+    pub breaking: usize,
+    pub markdown_path: Option<String>,
+    pub json_path: Option<String>,
+}
@@ -0,0 +1,147 @@
+use std::{error::Error, fmt, io};
+
+use serde::{Deserialize, Serialize};
+
+// yapi 接口错误分类
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum YapiErrorKind {
+    // token 无效或已过期
+    TokenInvalid,
+    // 没有项目权限
+    NoPermission,
+    // 项目、分类或接口不存在
+    NotFound,
+    // 其他 errcode 非 0 的业务错误
+    Api,
+    // 响应状态码非 2xx 且不是 yapi 的响应
+    Http,
+    // 连接失败或超时
+    Network,
+    // 其他请求错误，如地址不合法
+    Request,
+    // 响应无法解析
+    Decode,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct YapiError {
+    pub kind: YapiErrorKind,
+    // 出错的操作，如 "获取接口详情"
+    pub action: String,
+    // http 状态码，网络错误时为空
+    pub status: Option<u16>,
+    // yapi 返回的 errcode
+    pub errcode: Option<i64>,
+    // yapi 返回的 errmsg 或底层错误信息
+    pub errmsg: String,
+    // 已尝试的次数
+    pub attempts: u32,
+}
+
+impl YapiError {
+    pub fn new(kind: YapiErrorKind, action: &str, status: Option<u16>, errmsg: String) -> Self {
+        YapiError {
+            kind,
+            action: action.to_string(),
+            status,
+            errcode: None,
+            errmsg,
+            attempts: 1,
+        }
+    }
+
+    // 按 errcode 与 errmsg 识别已知的错误
+    pub fn from_errcode(action: &str, status: u16, errcode: i64, errmsg: String) -> Self {
+        let kind = match errcode {
+            40011 => YapiErrorKind::TokenInvalid,
+            40033 => YapiErrorKind::NoPermission,
+            490 => YapiErrorKind::NotFound,
+            _ if errmsg.contains("token") => YapiErrorKind::TokenInvalid,
+            _ if errmsg.contains("权限") => YapiErrorKind::NoPermission,
+            _ if errmsg.contains("不存在") => YapiErrorKind::NotFound,
+            _ => YapiErrorKind::Api,
+        };
+
+        YapiError {
+            kind,
+            action: action.to_string(),
+            status: Some(status),
+            errcode: Some(errcode),
+            errmsg,
+            attempts: 1,
+        }
+    }
+
+    // 连接失败、超时、5xx 与 429 可以重试，4xx 与 token 无效等不重试
+    pub fn is_retryable(&self) -> bool {
+        match self.kind {
+            YapiErrorKind::Network => true,
+            YapiErrorKind::Http => self
+                .status
+                .map_or(false, |status| status >= 500 || status == 429),
+            _ => false,
+        }
+    }
+
+    // 从 io::Error 中取出 yapi 错误
+    pub fn from_io_error(error: &io::Error) -> Option<&YapiError> {
+        error.get_ref()?.downcast_ref::<YapiError>()
+    }
+
+    fn get_kind_desc(&self) -> &'static str {
+        match self.kind {
+            YapiErrorKind::TokenInvalid => "token 无效",
+            YapiErrorKind::NoPermission => "没有权限",
+            YapiErrorKind::NotFound => "不存在",
+            YapiErrorKind::Api => "yapi 返回错误",
+            YapiErrorKind::Http => "请求失败",
+            YapiErrorKind::Network => "网络错误",
+            YapiErrorKind::Request => "请求错误",
+            YapiErrorKind::Decode => "响应解析失败",
+        }
+    }
+}
+
+// 获取接口详情失败: token 无效: 请登录... (errcode: 40011, HTTP 200)
+impl fmt::Display for YapiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}失败: {}: {}", self.action, self.get_kind_desc(), self.errmsg)?;
+
+        let mut details = vec![];
+        if let Some(errcode) = self.errcode {
+            details.push(format!("errcode: {}", errcode));
+        }
+        if let Some(status) = self.status {
+            details.push(format!("HTTP {}", status));
+        }
+        if self.attempts > 1 {
+            details.push(format!("已尝试 {} 次", self.attempts));
+        }
+        if !details.is_empty() {
+            write!(f, " ({})", details.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for YapiError {}
+
+impl From<YapiError> for io::Error {
+    fn from(error: YapiError) -> Self {
+        let kind = match error.kind {
+            YapiErrorKind::TokenInvalid | YapiErrorKind::NoPermission => {
+                io::ErrorKind::PermissionDenied
+            }
+            YapiErrorKind::NotFound => io::ErrorKind::NotFound,
+            YapiErrorKind::Network => io::ErrorKind::ConnectionAborted,
+            YapiErrorKind::Decode => io::ErrorKind::InvalidData,
+            YapiErrorKind::Api | YapiErrorKind::Http | YapiErrorKind::Request => {
+                io::ErrorKind::Other
+            }
+        };
+
+        io::Error::new(kind, error)
+    }
+}
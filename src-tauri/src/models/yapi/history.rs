@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+use super::interface::InterfaceData;
+
+// 接口详情的一个历史版本，内容相同的版本只保留一份
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct HistoryVersion {
+    // 版本号：同步时间戳-内容哈希
+    pub version: String,
+    pub hash: String,
+    // 同步时间戳，单位秒
+    pub synced_at: u64,
+    pub up_time: Option<u64>,
+    pub data: InterfaceData,
+}
+
+// 列出版本时不返回接口详情
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct HistoryEntry {
+    pub version: String,
+    pub hash: String,
+    pub synced_at: u64,
+    pub up_time: Option<u64>,
+}
+
+impl From<&HistoryVersion> for HistoryEntry {
+    fn from(history_version: &HistoryVersion) -> Self {
+        Self {
+            version: history_version.version.clone(),
+            hash: history_version.hash.clone(),
+            synced_at: history_version.synced_at,
+            up_time: history_version.up_time,
+        }
+    }
+}
@@ -14,8 +14,12 @@ pub struct InterfaceData {
     pub req_params: Option<Vec<Value>>,
     pub req_body_form: Option<Vec<Value>>,
     pub req_body_type: Option<String>,
+    pub req_headers: Option<Vec<Value>>,
+    pub res_body_type: Option<String>,
     pub res_body: Option<String>,
     pub method: String,
+    // yapi 中接口的更新时间，文件导入的接口为空
+    pub up_time: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -39,7 +43,7 @@ pub enum FormType {
     Json,
 }
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(PartialEq, Clone, Debug, Serialize)]
 pub enum ObjectType {
     Object,
     Array,
@@ -51,22 +55,23 @@ pub enum WebType {
     Response,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum JsonValue {
     ObjectLike(ObjectLike),
     Atom(Atom),
     Null,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Atom {
     pub value: String,
     pub required: bool,
     pub key: String,
     pub description: String,
+    pub format: Option<String>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Node {
     pub interface_name: String,
     pub key: String,
@@ -75,7 +80,7 @@ pub struct Node {
     pub description: String,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ObjectLike {
     pub interface_name: String,
     pub nodes: Vec<Node>,
@@ -85,7 +90,7 @@ pub struct ObjectLike {
     pub description: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Root {
     pub interface_name: String,
     pub interface_desc: String,
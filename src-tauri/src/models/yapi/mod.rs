@@ -3,4 +3,10 @@ pub mod project;
 pub mod web_response;
 pub mod category;
 pub mod interface;
-pub mod queue;
\ No newline at end of file
+pub mod queue;
+pub mod error;
+pub mod cache;
+pub mod provenance;
+pub mod diff;
+pub mod history;
+pub mod check;
\ No newline at end of file
@@ -4,4 +4,113 @@ use serde::{Deserialize, Serialize};
 pub struct YapiProjectBaseInfo { 
     pub _id: u32,
     pub name: String
-}
\ No newline at end of file
+}
+
+// 同步项目目录的结果
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ProjectDiscoveryReport {
+    pub project_id: String,
+    pub added: Vec<InterfaceChange>,
+    pub renamed: Vec<InterfaceRename>,
+    // yapi 中已删除的接口，本地标记为孤立而不直接删除
+    pub removed: Vec<InterfaceChange>,
+    // 移动到其他分类的接口
+    pub moved: Vec<InterfaceMove>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct InterfaceChange {
+    pub id: String,
+    pub cat_id: String,
+    pub name: String,
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct InterfaceMove {
+    pub id: String,
+    pub name: String,
+    pub old_cat_id: String,
+    pub new_cat_id: String,
+    pub old_path: Option<String>,
+    pub new_path: Option<String>,
+}
+
+// 孤立接口的处理方式
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OrphanAction {
+    // 删除生成的文件并从 yapi.json 中移除
+    Delete,
+    // 保留文件，把其中的类型标记为废弃
+    Deprecate,
+}
+
+// 处理孤立或移动接口时涉及的文件
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ReconcileReport {
+    pub files: Vec<String>,
+    // 未处理的文件及原因
+    pub skipped: Vec<String>,
+}
+
+// 名称或路径发生变化的接口
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct InterfaceRename {
+    pub id: String,
+    pub cat_id: String,
+    pub old_name: String,
+    pub new_name: String,
+    pub old_path: Option<String>,
+    pub new_path: Option<String>,
+}
+
+// 增量同步的结果
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct InterfaceSyncReport {
+    pub project_id: String,
+    // 加入队列的接口数
+    pub enqueued: usize,
+    // 更新时间未变化而跳过的接口数
+    pub skipped: usize,
+    // 锁定而跳过的接口数
+    pub locked: usize,
+    // 刷新项目目录的结果，文件导入的项目为空
+    pub discovery: Option<ProjectDiscoveryReport>,
+}
+
+// yapi 项目与本地 yapi.json 的对比结果，对比本身不修改本地配置
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct RemoteInterfaceReport {
+    pub project_id: String,
+    // yapi 中有、本地未记录的接口
+    pub added: Vec<InterfaceChange>,
+    // 生成后 yapi 中又有更新的接口
+    pub changed: Vec<InterfaceChange>,
+    // 名称或路径发生变化的接口
+    pub renamed: Vec<InterfaceRename>,
+    // 已记录但从未生成过的接口
+    pub ungenerated: Vec<InterfaceChange>,
+    // 加入队列的新增接口数
+    pub enqueued: usize,
+}
+
+// 上次生成后在 yapi 中改变了路径的接口
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PathRename {
+    pub id: String,
+    pub name: String,
+    pub old_path: String,
+    pub new_path: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct PathRenameReport {
+    pub renames: Vec<PathRename>,
+    // 移动后的生成文件
+    pub moved: Vec<String>,
+    // 更新了 import 的请求文件
+    pub rewritten: Vec<String>,
+    // 未处理的文件及原因
+    pub skipped: Vec<String>,
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+// 生成文件首行记录的来源信息
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Provenance {
+    pub project_id: u32,
+    pub interface_id: u32,
+    pub up_time: Option<u64>,
+    // 生成文件的 yapi-to-ts 版本
+    pub version: String,
+    // 首行之后内容的哈希，用于判断生成后是否被手动修改
+    pub hash: String,
+}
@@ -14,15 +14,28 @@ use tokio::{sync::Semaphore, time::sleep};
 use crate::services::{
     global_config::get_global_config,
     log::log,
-    yapi::interface::{fetch_interface_detail, get_interface_ts_string},
+    source::fetch_interface_detail,
+    yapi::{
+        config::{get_interface_lock_reason, get_project_config},
+        interface::get_resolved_interface,
+    },
 };
 
-use super::interface::{InterfaceData, InterfaceFetchParams};
+use super::{
+    error::YapiError,
+    interface::{InterfaceData, InterfaceFetchParams},
+};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ResolvedInterface {
     pub interface: InterfaceData,
     pub ts_string: String,
+    // zod schema 单独成文件时的内容
+    pub schema_string: Option<String>,
+    // 配置了 dart_path 时生成的 dart 内容
+    pub dart_string: Option<String>,
+    // 配置了 go_path 时生成的 go 内容
+    pub go_string: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -43,6 +56,10 @@ pub struct EmitMessage {
     pub msg: String,
     pub is_success: bool, // 0:success,1:fail
     pub resolved_interface: Option<ResolvedInterface>,
+    // yapi 返回的错误，包含 errcode、errmsg 与 http 状态码
+    pub error: Option<YapiError>,
+    // 获取接口详情时的请求次数
+    pub attempts: u32,
 }
 
 impl Queue {
@@ -89,19 +106,41 @@ impl Queue {
                 Some(fetch_interface_params) => {
                     tokio::spawn(async move {
                         let app_handle = app_handle.lock().await.clone();
+                        let project_config =
+                            get_project_config(&fetch_interface_params.source_path);
+
+                        // 加入队列后接口可能被锁定，执行时再检查一次
+                        let interface_id = fetch_interface_params.interface_id.to_string();
+                        let lock_reason = project_config.as_ref().ok().and_then(|project_config| {
+                            get_interface_lock_reason(project_config, Some(&interface_id), None)
+                        });
+                        if let Some(reason) = lock_reason {
+                            queue_log(
+                                &app_handle,
+                                None,
+                                format!("跳过接口 {}：{}", interface_id, reason),
+                                None,
+                                0,
+                            );
+                            // 补充一个令牌
+                            sem.add_permits(1);
+                            return;
+                        }
 
                         match fetch_interface_detail(fetch_interface_params, &app_handle).await {
-                            Ok(detail) => match get_interface_ts_string(&detail) {
-                                Ok(ts_string) => {
-                                    let title = detail.title.clone();
+                            Ok(detail) => match project_config
+                                .map_err(|e| e.to_string())
+                                .and_then(|project_config| {
+                                    get_resolved_interface(detail.value, &project_config)
+                                }) {
+                                Ok(resolved_interface) => {
+                                    let title = resolved_interface.interface.title.clone();
                                     queue_log(
                                         &app_handle,
-                                        Some(ResolvedInterface {
-                                            interface: detail,
-                                            ts_string,
-                                        }),
+                                        Some(resolved_interface),
                                         format!("接口 {} 已完成！", title),
-                                        true,
+                                        None,
+                                        detail.attempts,
                                     )
                                 }
                                 Err(e) => {
@@ -109,17 +148,22 @@ impl Queue {
                                         &app_handle,
                                         None,
                                         format!("接口转换失败: {}", e.to_string()),
-                                        false,
+                                        None,
+                                        detail.attempts,
                                     );
                                 }
                             },
                             Err(e) => {
+                                let error = YapiError::from_io_error(&e).cloned();
+                                let attempts = error.as_ref().map_or(1, |error| error.attempts);
+
                                 log(&app_handle, format!("接口请求失败：{}", e.to_string()));
                                 queue_log(
                                     &app_handle,
                                     None,
                                     format!("接口请求失败：{}", e.to_string()),
-                                    false,
+                                    error,
+                                    attempts,
                                 );
                             }
                         }
@@ -150,19 +194,22 @@ impl Queue {
     }
 }
 
-fn queue_log(
+pub fn queue_log(
     app_handle: &AppHandle,
     resolved_interface: Option<ResolvedInterface>,
     msg: String,
-    is_success: bool,
+    error: Option<YapiError>,
+    attempts: u32,
 ) {
     app_handle
         .emit_all(
             "queue_log",
             EmitMessage {
                 msg,
-                is_success,
+                is_success: resolved_interface.is_some(),
                 resolved_interface,
+                error,
+                attempts,
             },
         )
         .unwrap();
@@ -6,4 +6,11 @@ pub struct YapiResponse<T> {
     pub errcode : u32,
     pub errmsg : String,
     pub data : T
-}
\ No newline at end of file
+}
+
+// 请求结果与实际尝试的次数
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Attempted<T> {
+    pub value: T,
+    pub attempts: u32,
+}
@@ -10,4 +10,24 @@ pub fn string_to_path_buf(input: String) -> PathBuf {
 
 pub fn string_to_u8_slice(input: &String) -> &[u8] {
     input.as_bytes()
-}
\ No newline at end of file
+}
+
+// 由字符串生成稳定的 id（FNV-1a），最高位置 1 以区别于 yapi 的自增 id
+pub fn string_to_hash_id(input: &str) -> u32 {
+    let mut hash: u32 = 0x811c_9dc5;
+    for byte in input.as_bytes() {
+        hash ^= *byte as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    hash | 0x8000_0000
+}
+
+// 文件内容的哈希（64 位 FNV-1a），以十六进制表示
+pub fn string_to_content_hash(input: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in input.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
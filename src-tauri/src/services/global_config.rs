@@ -58,9 +58,11 @@ pub fn get_global_config(app_handle: &AppHandle) -> Result<GlobalConfig, io::Err
 pub fn write_config(app_handle: &AppHandle, contents: &GlobalConfig) -> Result<(), io::Error> {
     let local_data_dir = get_local_data_dir(app_handle);
     if let Some(local_dir) = local_data_dir {
+        // 配置内容可能变短（如删除请求头），需要截断
         let mut file = OpenOptions::new()
             .write(true)
             .create(true)
+            .truncate(true)
             .open(local_dir.join(CONFIG_NAME))?;
         file.write_all(json!(contents).to_string().as_bytes())?;
         Ok(())
@@ -5,4 +5,6 @@ pub mod conversion;
 pub mod log;
 pub mod yapi;
 pub mod reqwest;
-pub mod notification;
\ No newline at end of file
+pub mod notification;
+pub mod openapi;
+pub mod source;
\ No newline at end of file
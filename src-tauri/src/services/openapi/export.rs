@@ -0,0 +1,430 @@
+use std::{
+    collections::BTreeSet,
+    fs, io,
+    path::PathBuf,
+};
+
+use serde_json::{json, Map, Value};
+use tauri::AppHandle;
+
+use crate::{
+    models::{
+        openapi::{OpenApiExportReport, OpenApiFormat},
+        yapi::interface::{FormType, InterfaceData, InterfaceFetchParams},
+    },
+    services::{
+        source::fetch_interface_detail,
+        yapi::{
+            config::get_project_config,
+            resolver::common::{get_json, get_path_arr, get_req_body_type},
+        },
+    },
+};
+
+pub const OPENAPI_FILE_NAME: &str = "openapi";
+
+const OPENAPI_VERSION: &str = "3.0.3";
+
+// OpenAPI 3.0 中 schema 允许的字段，yapi 的 json schema 中其余字段（如 mock、$schema）会被去掉
+const SCHEMA_KEYS: [&str; 24] = [
+    "type",
+    "format",
+    "title",
+    "description",
+    "default",
+    "example",
+    "enum",
+    "properties",
+    "items",
+    "required",
+    "additionalProperties",
+    "nullable",
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "minItems",
+    "maxItems",
+    "uniqueItems",
+    "oneOf",
+    "anyOf",
+];
+
+// 把 yapi.json 中记录的全部接口导出为一份 OpenAPI 3 文档
+pub async fn export_openapi_document(
+    source_path: &str,
+    target_path: &str,
+    format: OpenApiFormat,
+    app_handle: &AppHandle,
+) -> Result<OpenApiExportReport, io::Error> {
+    let project_config = get_project_config(source_path)?;
+    let mut paths = Map::new();
+    let mut tags = BTreeSet::new();
+    let mut total = 0;
+    let mut exported = 0;
+    let mut failed = vec![];
+
+    for project in &project_config.project_list {
+        for category in &project.categories {
+            for interface in &category.interfaces {
+                // yapi 中已删除的接口不再导出
+                if interface.orphaned == Some(true) {
+                    continue;
+                }
+                total += 1;
+
+                let interface_id = match interface.id.parse::<u32>() {
+                    Ok(interface_id) => interface_id,
+                    Err(_) => {
+                        failed.push(format!("{}: 非法的接口 id {}", interface.name, interface.id));
+                        continue;
+                    }
+                };
+
+                let fetch_interface_params = InterfaceFetchParams {
+                    interface_id,
+                    token: project.token.clone(),
+                    source_path: source_path.to_string(),
+                };
+
+                match fetch_interface_detail(fetch_interface_params, app_handle).await {
+                    Ok(detail) => {
+                        add_operation(&mut paths, &detail.value, &category.name);
+                        tags.insert(category.name.clone());
+                        exported += 1;
+                    }
+                    Err(e) => failed.push(format!("{}: {}", interface.name, e)),
+                }
+            }
+        }
+    }
+
+    let title = project_config
+        .project_list
+        .iter()
+        .filter_map(|project| project.project_name.clone())
+        .collect::<Vec<_>>()
+        .join(" / ");
+
+    let document = json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": if title.is_empty() { String::from("yapi") } else { title },
+            "version": "1.0.0"
+        },
+        "tags": tags.iter().map(|name| json!({ "name": name })).collect::<Vec<_>>(),
+        "paths": paths
+    });
+
+    let (extension, contents) = match format {
+        OpenApiFormat::Json => ("json", serde_json::to_string_pretty(&document)?),
+        OpenApiFormat::Yaml => (
+            "yaml",
+            serde_yaml::to_string(&document)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?,
+        ),
+    };
+    let file_path =
+        PathBuf::from(target_path).join(format!("{}.{}", OPENAPI_FILE_NAME, extension));
+
+    fs::write(&file_path, contents)?;
+
+    Ok(OpenApiExportReport {
+        file_path: file_path.to_string_lossy().to_string(),
+        total,
+        exported,
+        failed,
+    })
+}
+
+fn add_operation(paths: &mut Map<String, Value>, data: &InterfaceData, tag: &str) {
+    let path = get_openapi_path(&data.path);
+    let path_item = paths
+        .entry(path)
+        .or_insert_with(|| Value::Object(Map::new()));
+
+    if let Some(path_item) = path_item.as_object_mut() {
+        path_item.insert(data.method.to_lowercase(), get_openapi_operation(data, tag));
+    }
+}
+
+// /api/user/:id?type=1 -> /api/user/{id}
+pub fn get_openapi_path(raw_path: &str) -> String {
+    let path = raw_path.split('?').next().unwrap_or_default();
+    let segments: Vec<_> = get_path_arr(path.to_string())
+        .iter()
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) => format!("{{{}}}", name),
+            None => segment.clone(),
+        })
+        .collect();
+
+    format!("/{}", segments.join("/"))
+}
+
+// 接口详情转换为 OpenAPI operation
+pub fn get_openapi_operation(data: &InterfaceData, tag: &str) -> Value {
+    let mut operation = Map::new();
+    operation.insert("tags".to_string(), json!([tag]));
+    operation.insert("summary".to_string(), json!(data.title));
+    operation.insert("operationId".to_string(), json!(get_operation_id(data)));
+
+    let mut parameters = vec![];
+    let path = get_openapi_path(&data.path);
+
+    for param in data.req_params.iter().flatten() {
+        parameters.push(get_parameter(param, "path"));
+    }
+    // yapi 中未声明的路径参数也需要补齐，否则文档不合法
+    for segment in get_path_arr(path) {
+        if let Some(name) = segment
+            .strip_prefix('{')
+            .and_then(|s| s.strip_suffix('}'))
+        {
+            if !parameters
+                .iter()
+                .any(|p| p["in"] == "path" && p["name"] == name)
+            {
+                parameters.push(json!({
+                    "name": name,
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "string" }
+                }));
+            }
+        }
+    }
+    for query in data.req_query.iter().flatten() {
+        parameters.push(get_parameter(query, "query"));
+    }
+    // GET 与 DELETE 没有请求体，yapi 中记录的表单字段作为查询参数
+    let has_request_body = !matches!(data.method.to_uppercase().as_str(), "GET" | "DELETE");
+    if !has_request_body {
+        for field in data.req_body_form.iter().flatten() {
+            let name = field.get("name").and_then(|n| n.as_str()).unwrap_or_default();
+            if name.is_empty()
+                || parameters
+                    .iter()
+                    .any(|p| p["in"] == "query" && p["name"] == name)
+            {
+                continue;
+            }
+            parameters.push(get_parameter(field, "query"));
+        }
+    }
+    for header in data.req_headers.iter().flatten() {
+        let name = header.get("name").and_then(|n| n.as_str()).unwrap_or_default();
+        // Content-Type 由 requestBody 描述
+        if name.is_empty() || name.eq_ignore_ascii_case("content-type") {
+            continue;
+        }
+        parameters.push(get_parameter(header, "header"));
+    }
+
+    if !parameters.is_empty() {
+        operation.insert("parameters".to_string(), json!(parameters));
+    }
+
+    if let Some(request_body) = get_request_body(data).filter(|_| has_request_body) {
+        operation.insert("requestBody".to_string(), request_body);
+    }
+
+    operation.insert("responses".to_string(), get_responses(data));
+
+    Value::Object(operation)
+}
+
+fn get_operation_id(data: &InterfaceData) -> String {
+    let path_name = get_path_arr(data.path.split('?').next().unwrap_or_default().to_string())
+        .iter()
+        .map(|segment| {
+            segment
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric())
+                .collect::<String>()
+        })
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+
+    format!("{}_{}", data.method.to_lowercase(), path_name)
+}
+
+fn get_parameter(value: &Value, location: &str) -> Value {
+    let name = value.get("name").and_then(|n| n.as_str()).unwrap_or_default();
+    let required = location == "path" || is_yapi_required(value);
+    let mut parameter = json!({
+        "name": name,
+        "in": location,
+        "required": required,
+        "schema": { "type": "string" }
+    });
+
+    if let Some(desc) = value.get("desc").and_then(|d| d.as_str()) {
+        if !desc.is_empty() {
+            parameter["description"] = json!(desc);
+        }
+    }
+    if let Some(example) = value.get("example").and_then(|e| e.as_str()) {
+        if !example.is_empty() {
+            parameter["example"] = json!(example);
+        }
+    }
+
+    parameter
+}
+
+fn get_request_body(data: &InterfaceData) -> Option<Value> {
+    match get_req_body_type(data) {
+        FormType::Form => {
+            let form = data.req_body_form.as_ref().filter(|form| !form.is_empty())?;
+            let mut properties = Map::new();
+            let mut required = vec![];
+            let mut has_file = false;
+
+            for field in form {
+                let name = field.get("name").and_then(|n| n.as_str()).unwrap_or_default();
+                if name.is_empty() {
+                    continue;
+                }
+                let is_file = field.get("type").and_then(|t| t.as_str()) == Some("file");
+                has_file = has_file || is_file;
+
+                let mut schema = if is_file {
+                    json!({ "type": "string", "format": "binary" })
+                } else {
+                    json!({ "type": "string" })
+                };
+                if let Some(desc) = field.get("desc").and_then(|d| d.as_str()) {
+                    if !desc.is_empty() {
+                        schema["description"] = json!(desc);
+                    }
+                }
+                if is_yapi_required(field) {
+                    required.push(name.to_string());
+                }
+                properties.insert(name.to_string(), schema);
+            }
+
+            let mut schema = json!({ "type": "object", "properties": properties });
+            if !required.is_empty() {
+                schema["required"] = json!(required);
+            }
+            let content_type = if has_file {
+                "multipart/form-data"
+            } else {
+                "application/x-www-form-urlencoded"
+            };
+
+            Some(json!({ "content": { content_type: { "schema": schema } } }))
+        }
+        FormType::Json => {
+            let schema = get_json(data.req_body_other.clone()?);
+            if schema.is_null() {
+                return None;
+            }
+
+            Some(json!({
+                "content": { "application/json": { "schema": get_openapi_schema(&schema) } }
+            }))
+        }
+    }
+}
+
+fn get_responses(data: &InterfaceData) -> Value {
+    let res_body = data.res_body.clone().unwrap_or_default();
+    let is_json = data.res_body_type.as_deref().unwrap_or("json") == "json";
+    let schema = get_json(res_body.clone());
+
+    let content = if is_json && !schema.is_null() {
+        Some(json!({ "application/json": { "schema": get_openapi_schema(&schema) } }))
+    } else if !res_body.is_empty() {
+        Some(json!({ "text/plain": { "schema": { "type": "string" }, "example": res_body } }))
+    } else {
+        None
+    };
+
+    match content {
+        Some(content) => json!({ "200": { "description": "成功", "content": content } }),
+        None => json!({ "200": { "description": "成功" } }),
+    }
+}
+
+// yapi 的 json schema 转换为 OpenAPI 3.0 的 schema
+pub fn get_openapi_schema(value: &Value) -> Value {
+    let object = match value.as_object() {
+        Some(object) => object,
+        None => return json!({}),
+    };
+    let mut schema = Map::new();
+
+    for (key, value) in object {
+        if !SCHEMA_KEYS.contains(&key.as_str()) {
+            continue;
+        }
+
+        match key.as_str() {
+            // json schema 允许 type 为数组，OpenAPI 3.0 使用 nullable 表达
+            "type" => match value {
+                Value::Array(types) => {
+                    let mut types = types.iter().filter_map(|t| t.as_str());
+                    let first = types.clone().find(|t| *t != "null").unwrap_or("string");
+                    schema.insert(key.clone(), json!(first));
+                    if types.any(|t| t == "null") {
+                        schema.insert("nullable".to_string(), json!(true));
+                    }
+                }
+                // 导入时无法确定的类型记为 any，OpenAPI 中省略 type 即可
+                Value::String(schema_type) if schema_type == "any" => {}
+                _ => {
+                    schema.insert(key.clone(), value.clone());
+                }
+            },
+            "properties" => {
+                let properties: Map<String, Value> = value
+                    .as_object()
+                    .map(|properties| {
+                        properties
+                            .iter()
+                            .map(|(name, property)| (name.clone(), get_openapi_schema(property)))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                schema.insert(key.clone(), Value::Object(properties));
+            }
+            "items" | "additionalProperties" if value.is_object() => {
+                schema.insert(key.clone(), get_openapi_schema(value));
+            }
+            "oneOf" | "anyOf" => {
+                let list: Vec<_> = value
+                    .as_array()
+                    .map(|list| list.iter().map(get_openapi_schema).collect())
+                    .unwrap_or_default();
+                schema.insert(key.clone(), json!(list));
+            }
+            // OpenAPI 3.0 要求 required 不能为空数组
+            "required" => {
+                if value.as_array().map_or(false, |list| !list.is_empty()) {
+                    schema.insert(key.clone(), value.clone());
+                }
+            }
+            _ => {
+                schema.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    Value::Object(schema)
+}
+
+fn is_yapi_required(value: &Value) -> bool {
+    match value.get("required") {
+        Some(Value::String(required)) => required == "1",
+        Some(Value::Bool(required)) => *required,
+        Some(Value::Number(required)) => required.as_u64() == Some(1),
+        _ => false,
+    }
+}
@@ -1,12 +1,14 @@
 use std::{
-    fs::{self, File},
-    io::{self, BufRead, BufReader, Write},
+    fs,
+    io::{self, Write},
     path::{Path, PathBuf},
 };
 
-use crate::models::file::FileTree;
+use crate::models::{file::FileTree, yapi::config::YapiConfig};
 
-use super::yapi::{config::get_project_config, resolver::common::get_legal_name};
+use super::yapi::{
+    config::get_project_config, provenance::is_provenance_line, resolver::common::get_legal_name,
+};
 
 pub fn get_file_tree(
     source_path: &str,
@@ -54,39 +56,63 @@ pub fn get_file_tree(
 
 pub fn get_request_ts_string(source_path: &str, path: &PathBuf) -> Result<String, io::Error> {
     let project_config = get_project_config(source_path)?;
-    let sub_path = get_type_relative_path(source_path, project_config.types_path, path);
+    let sub_path = get_type_relative_path(source_path, project_config.types_path.clone(), path);
+
+    let mut files = vec![];
+
+    for dir in fs::read_dir(path)?.into_iter() {
+        let dir = dir?;
+        if !dir.file_type()?.is_file() {
+            continue;
+        }
+        // 非文本文件不可能是 type 文件
+        if let Ok(content) = fs::read_to_string(dir.path()) {
+            files.push((dir.path(), content));
+        }
+    }
+
+    Ok(get_request_ts_string_from_files(
+        &project_config,
+        &sub_path,
+        files,
+    ))
+}
+
+// 由目录下 type 文件的内容生成 request 字符串，按文件名排序保证每次生成的结果一致
+pub fn get_request_ts_string_from_files(
+    project_config: &YapiConfig,
+    sub_path: &Option<PathBuf>,
+    mut files: Vec<(PathBuf, String)>,
+) -> String {
+    files.sort_by(|a, b| a.0.cmp(&b.0));
 
     let mut ts_string = format!("{}\n", project_config.header_template.clone());
 
     let mut import_list: Vec<String> = vec![];
     let mut export_list: Vec<String> = vec![];
 
-    for dir in fs::read_dir(path)?.into_iter() {
-        let dir = dir?;
-        let file_type = dir.file_type()?;
-        let file_path = dir.path();
-        let file_name_without_ext = get_file_name_without_ext(&file_path);
-
-        if file_type.is_file() {
-            if check_file(&file_path, &file_name_without_ext) {
-                op_import_list(
-                    project_config.type_import_template.clone(),
-                    &sub_path,
-                    &file_name_without_ext,
-                    &mut import_list,
-                );
-                op_export_list(
-                    project_config.request_template.clone(),
-                    &file_path,
-                    &sub_path,
-                    &mut export_list,
-                );
-            }
+    for (file_path, content) in &files {
+        let file_name_without_ext = get_file_name_without_ext(file_path);
+
+        if check_file(content, &file_name_without_ext) {
+            op_import_list(
+                project_config.type_import_template.clone(),
+                sub_path,
+                &file_name_without_ext,
+                &mut import_list,
+            );
+            op_export_list(
+                project_config.request_template.clone(),
+                file_path,
+                content,
+                sub_path,
+                &mut export_list,
+            );
         }
     }
 
     if import_list.len() == 0 && export_list.len() == 0 {
-        return Ok(String::new());
+        return String::new();
     }
 
     for str in import_list {
@@ -97,7 +123,7 @@ pub fn get_request_ts_string(source_path: &str, path: &PathBuf) -> Result<String
         ts_string += str.as_str();
     }
 
-    Ok(ts_string)
+    ts_string
 }
 
 pub fn write_request_ts_file(
@@ -106,26 +132,37 @@ pub fn write_request_ts_file(
     content: String,
 ) -> Result<(), io::Error> {
     let project_config = get_project_config(source_path)?;
-    let sub_path = get_type_relative_path(source_path, project_config.types_path, path);
-    let write_path = get_write_path(
-        project_config.request_path,
-        source_path,
-        project_config.file_name_template,
-        &sub_path,
-    );
+    let write_path = get_request_file_path(source_path, &project_config, path);
     let parent = write_path.parent().unwrap();
 
     fs::create_dir_all(parent)?;
 
-    let mut file = fs::File::create(format!("{}.ts", write_path.to_str().unwrap()))?;
+    let mut file = fs::File::create(write_path)?;
 
     file.write_all(content.as_bytes())?;
 
     Ok(())
 }
 
+// types 目录对应的 request 文件路径
+pub fn get_request_file_path(
+    source_path: &str,
+    project_config: &YapiConfig,
+    path: &PathBuf,
+) -> PathBuf {
+    let sub_path = get_type_relative_path(source_path, project_config.types_path.clone(), path);
+    let write_path = get_write_path(
+        project_config.request_path.clone(),
+        source_path,
+        project_config.file_name_template.clone(),
+        &sub_path,
+    );
+
+    PathBuf::from(format!("{}.ts", write_path.to_str().unwrap()))
+}
+
 // 获取 type 文件的相对路径
-fn get_type_relative_path(source_path: &str, type_path: String, path: &PathBuf) -> Option<PathBuf> {
+pub fn get_type_relative_path(source_path: &str, type_path: String, path: &PathBuf) -> Option<PathBuf> {
     let types_root_path = PathBuf::from(source_path).join(type_path);
     match path == &types_root_path {
         true => None,
@@ -160,26 +197,11 @@ fn get_write_path(
 }
 
 // 检查用于生成 request 的 type 文件是否有 Request/Response interface
-fn check_file(file_path: &PathBuf, file_name_without_ext: &String) -> bool {
+fn check_file(content: &str, file_name_without_ext: &String) -> bool {
     let req = format!("{}Request", get_legal_name(file_name_without_ext));
     let resp = format!("{}Response", get_legal_name(file_name_without_ext));
 
-    if is_string_in_file(&file_path, &req) && is_string_in_file(&file_path, &resp) {
-        return true;
-    }
-    false
-}
-
-pub fn is_string_in_file(ts_file: &PathBuf, string: &str) -> bool {
-    let file = File::open(ts_file).unwrap();
-    let reader = BufReader::new(file);
-
-    for line in reader.lines() {
-        if line.unwrap().contains(string) {
-            return true;
-        }
-    }
-    false
+    content.contains(&req) && content.contains(&resp)
 }
 
 // 把import ts 定义字符串添加进import_list
@@ -204,10 +226,11 @@ fn op_import_list(
 fn op_export_list(
     request_template: String,
     file_path: &PathBuf,
+    content: &str,
     sub_path: &Option<PathBuf>,
     export_list: &mut Vec<String>,
 ) {
-    let comment = get_comment(file_path.clone());
+    let comment = get_comment(content);
 
     let sub_path_unix = get_sub_path_unix(sub_path);
 
@@ -239,15 +262,17 @@ fn get_sub_path_unix(sub_path: &Option<PathBuf>) -> String {
     }
 }
 
-fn get_comment(ts_file: PathBuf) -> String {
-    let file = File::open(ts_file).unwrap();
-    let reader = BufReader::new(file);
+fn get_comment(content: &str) -> String {
+    let mut iteror = content.lines();
+    let mut first_line = iteror.next().unwrap_or_default();
 
-    let mut iteror = reader.lines().into_iter();
-    let first_line = iteror.next().unwrap().unwrap();
+    // 跳过生成文件首行的来源信息
+    if is_provenance_line(first_line) {
+        first_line = iteror.next().unwrap_or_default();
+    }
 
     if first_line.starts_with("//") {
-        return first_line;
+        return first_line.to_string();
     }
 
     String::from("")
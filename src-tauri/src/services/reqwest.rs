@@ -1,34 +1,176 @@
-use std::io;
+use std::{collections::HashMap, fs, io, sync::RwLock, time::Duration};
 
-use reqwest::Client;
-use serde::de::DeserializeOwned;
-use tauri::AppHandle;
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Certificate, Client,
+};
+use tauri::{AppHandle, Manager};
 
-use super::global_config::get_global_config;
+use crate::models::global_config::{GlobalConfig, RetryPolicy};
 
-pub fn get_reqwest_client(app_handle: &AppHandle) -> Result<Client, io::Error> {
-    let global_config = get_global_config(app_handle)?;
+use super::{global_config::get_global_config, log::log};
 
-    let proxy = if let Some(proxy) = global_config.proxy {
-        if !proxy.is_empty() {
-            Some(reqwest::Proxy::all(proxy).unwrap())
-        } else {
-            None
+const PEM_CERTIFICATE_BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+
+// 共享的 http 客户端，复用连接池，网络配置变化时重建
+pub struct HttpClientState {
+    clients: RwLock<HttpClients>,
+}
+
+struct HttpClients {
+    global_config: GlobalConfig,
+    default: Client,
+    // 项目单独配置了代理时使用的客户端，按代理地址缓存
+    by_proxy: HashMap<String, Client>,
+}
+
+impl HttpClientState {
+    // 配置有误时记录日志并使用默认客户端，避免应用无法启动
+    pub fn new(app_handle: &AppHandle) -> HttpClientState {
+        let global_config = get_global_config(app_handle).unwrap_or_default();
+        let client = build_reqwest_client(&global_config).unwrap_or_else(|e| {
+            log(app_handle, format!("创建 http 客户端失败，使用默认配置：{}", e));
+            Client::new()
+        });
+
+        HttpClientState {
+            clients: RwLock::new(HttpClients {
+                global_config,
+                default: client,
+                by_proxy: HashMap::new(),
+            }),
+        }
+    }
+
+    // proxy 为项目配置的代理，为空时使用全局配置的客户端
+    pub fn get(&self, proxy: Option<&str>) -> Result<Client, io::Error> {
+        let proxy = match proxy.filter(|proxy| !proxy.is_empty()) {
+            Some(proxy) => proxy,
+            None => return Ok(self.read(|clients| clients.default.clone())),
+        };
+
+        if let Some(client) = self.read(|clients| clients.by_proxy.get(proxy).cloned()) {
+            return Ok(client);
+        }
+
+        let mut clients = self.clients.write().unwrap_or_else(|e| e.into_inner());
+        let mut global_config = clients.global_config.clone();
+        global_config.proxy = Some(proxy.to_string());
+        let client = build_reqwest_client(&global_config)?;
+        clients.by_proxy.insert(proxy.to_string(), client.clone());
+
+        Ok(client)
+    }
+
+    pub fn reload(&self, global_config: &GlobalConfig) -> Result<(), io::Error> {
+        let client = build_reqwest_client(global_config)?;
+        let mut clients = self.clients.write().unwrap_or_else(|e| e.into_inner());
+        *clients = HttpClients {
+            global_config: global_config.clone(),
+            default: client,
+            by_proxy: HashMap::new(),
+        };
+        Ok(())
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&HttpClients) -> T) -> T {
+        f(&self.clients.read().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+pub fn get_reqwest_client(app_handle: &AppHandle, proxy: Option<&str>) -> Result<Client, io::Error> {
+    app_handle.state::<HttpClientState>().get(proxy)
+}
+
+// 按全局配置创建客户端：代理、超时、默认请求头与证书
+pub fn build_reqwest_client(global_config: &GlobalConfig) -> Result<Client, io::Error> {
+    let mut builder = Client::builder();
+
+    if let Some(proxy) = global_config.proxy.as_ref().filter(|p| !p.is_empty()) {
+        let proxy = reqwest::Proxy::all(proxy).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("代理地址 {} 不合法：{}", proxy, e),
+            )
+        })?;
+        builder = builder.proxy(proxy);
+    }
+    if let Some(timeout_seconds) = global_config.timeout_seconds.filter(|t| *t > 0) {
+        builder = builder.timeout(Duration::from_secs(timeout_seconds));
+    }
+    if let Some(connect_timeout_seconds) = global_config.connect_timeout_seconds.filter(|t| *t > 0)
+    {
+        builder = builder.connect_timeout(Duration::from_secs(connect_timeout_seconds));
+    }
+    if let Some(headers) = &global_config.headers {
+        builder = builder.default_headers(get_header_map(headers.iter())?);
+    }
+    if let Some(ca_cert_path) = global_config.ca_cert_path.as_ref().filter(|p| !p.is_empty()) {
+        for certificate in get_certificates(ca_cert_path)? {
+            builder = builder.add_root_certificate(certificate);
         }
-    } else {
-        None
-    };
+    }
+    if global_config.accept_invalid_certs.unwrap_or(false) {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
 
-    if let Some(proxy) = proxy.clone() {
-        Ok(reqwest::Client::builder().proxy(proxy).build().unwrap())
-    } else {
-        Ok(reqwest::Client::new())
+    builder
+        .build()
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("创建 http 客户端失败：{}", e)))
+}
+
+fn get_header_map<'a>(
+    headers: impl Iterator<Item = (&'a String, &'a String)>,
+) -> Result<HeaderMap, io::Error> {
+    let mut header_map = HeaderMap::new();
+
+    for (name, value) in headers {
+        let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("请求头 {} 不合法", name),
+            )
+        })?;
+        let header_value = HeaderValue::from_str(value).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("请求头 {} 的值不合法", name),
+            )
+        })?;
+        header_map.insert(header_name, header_value);
     }
+
+    Ok(header_map)
+}
+
+// 读取 PEM 文件中的全部证书
+fn get_certificates(ca_cert_path: &str) -> Result<Vec<Certificate>, io::Error> {
+    let contents = fs::read_to_string(ca_cert_path)?;
+    let certificates = contents
+        .split(PEM_CERTIFICATE_BEGIN)
+        .skip(1)
+        .map(|block| {
+            Certificate::from_pem(format!("{}{}", PEM_CERTIFICATE_BEGIN, block).as_bytes())
+                .map_err(|e| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("解析证书 {} 失败：{}", ca_cert_path, e),
+                    )
+                })
+        })
+        .collect::<Result<Vec<_>, io::Error>>()?;
+
+    if certificates.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("证书文件 {} 中没有证书", ca_cert_path),
+        ));
+    }
+
+    Ok(certificates)
 }
 
-pub async fn get_data<T>(client: Client, url: String) -> Result<T, Box<dyn std::error::Error>>
-where
-    T: DeserializeOwned,
-{
-    Ok(client.get(url).send().await?.json::<T>().await?)
+// yapi 请求的重试策略，旧的配置文件中没有该字段时使用默认值
+pub fn get_retry_policy(app_handle: &AppHandle) -> Result<RetryPolicy, io::Error> {
+    Ok(get_global_config(app_handle)?.retry.unwrap_or_default())
 }
@@ -0,0 +1,333 @@
+use std::{fs, io, path::Path};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::Url;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::{
+    models::{
+        source::{SourceCategory, SourceProject},
+        yapi::interface::InterfaceData,
+    },
+    services::conversion::string_to_hash_id,
+};
+
+use super::{
+    get_source_file_path,
+    infer::{infer_response_schema, infer_schema_from_samples},
+};
+
+#[derive(Debug, Deserialize)]
+struct Har {
+    log: HarLog,
+}
+
+#[derive(Debug, Deserialize)]
+struct HarLog {
+    entries: Vec<HarEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HarEntry {
+    request: HarRequest,
+    response: HarResponse,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarRequest {
+    method: String,
+    url: String,
+    #[serde(default)]
+    query_string: Vec<HarParam>,
+    post_data: Option<HarPostData>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct HarParam {
+    name: String,
+    #[serde(default)]
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarPostData {
+    #[serde(default)]
+    mime_type: String,
+    text: Option<String>,
+    params: Option<Vec<HarParam>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HarResponse {
+    status: i64,
+    content: HarContent,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarContent {
+    mime_type: Option<String>,
+    text: Option<String>,
+    encoding: Option<String>,
+}
+
+// 同一方法与归一化路径下的全部请求记录
+struct HarEndpoint {
+    host: String,
+    method: String,
+    path: String,
+    path_params: Vec<String>,
+    query_samples: Vec<Vec<HarParam>>,
+    form_samples: Vec<Vec<HarParam>>,
+    request_samples: Vec<Value>,
+    response_samples: Vec<(bool, Value)>,
+}
+
+// 读取浏览器导出的 har 文件，按域名分类，推断接口的请求与响应
+pub fn load_source_project(source_path: &str, file_path: &str) -> Result<SourceProject, io::Error> {
+    let contents = fs::read_to_string(get_source_file_path(source_path, file_path))?;
+    let har: Har = serde_json::from_str(&contents).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("解析 har 文件失败: {}", e),
+        )
+    })?;
+
+    Ok(get_source_project(har, file_path))
+}
+
+fn get_source_project(har: Har, file_path: &str) -> SourceProject {
+    let project_id = string_to_hash_id(file_path);
+    let mut categories: Vec<SourceCategory> = vec![];
+
+    for endpoint in get_endpoints(har) {
+        let cat_id = string_to_hash_id(&format!("{}#{}", file_path, endpoint.host));
+        let interface_data = get_interface_data(&endpoint, project_id, cat_id, file_path);
+
+        match categories.iter_mut().find(|c| c.cat_id == cat_id) {
+            Some(category) => category.interfaces.push(interface_data),
+            None => categories.push(SourceCategory {
+                cat_id,
+                name: endpoint.host.clone(),
+                interfaces: vec![interface_data],
+            }),
+        }
+    }
+
+    let file_name = Path::new(file_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or(file_path.to_string());
+
+    SourceProject {
+        project_id,
+        name: format!("HAR {}", file_name),
+        categories,
+    }
+}
+
+// 仅保留请求或响应为 json 的记录，按方法与归一化路径分组
+fn get_endpoints(har: Har) -> Vec<HarEndpoint> {
+    let mut endpoints: Vec<HarEndpoint> = vec![];
+
+    for entry in har.log.entries {
+        let request = entry.request;
+        let method = request.method.to_uppercase();
+        let url = match Url::parse(&request.url) {
+            Ok(url) => url,
+            Err(_) => continue,
+        };
+        let request_mime_type = request
+            .post_data
+            .as_ref()
+            .map(|post_data| post_data.mime_type.clone())
+            .unwrap_or_default();
+        let response_mime_type = entry.response.content.mime_type.clone().unwrap_or_default();
+
+        // 跨域预检请求与静态资源不是接口
+        if method == "OPTIONS"
+            || !(request_mime_type.contains("json") || response_mime_type.contains("json"))
+        {
+            continue;
+        }
+
+        let host = url.host_str().unwrap_or("unknown").to_string();
+        let (path, path_params) = normalize_path(url.path());
+        let index = match endpoints
+            .iter()
+            .position(|e| e.host == host && e.method == method && e.path == path)
+        {
+            Some(index) => index,
+            None => {
+                endpoints.push(HarEndpoint {
+                    host,
+                    method,
+                    path,
+                    path_params,
+                    query_samples: vec![],
+                    form_samples: vec![],
+                    request_samples: vec![],
+                    response_samples: vec![],
+                });
+                endpoints.len() - 1
+            }
+        };
+        let endpoint = &mut endpoints[index];
+
+        endpoint.query_samples.push(request.query_string);
+
+        if let Some(post_data) = request.post_data {
+            if post_data.mime_type.contains("json") {
+                if let Some(sample) = post_data.text.as_deref().and_then(parse_json) {
+                    endpoint.request_samples.push(sample);
+                }
+            } else if let Some(params) = post_data.params {
+                endpoint.form_samples.push(params);
+            }
+        }
+
+        if response_mime_type.contains("json") {
+            if let Some(sample) = get_response_text(&entry.response.content)
+                .as_deref()
+                .and_then(parse_json)
+            {
+                let is_success = (200..300).contains(&entry.response.status);
+                endpoint.response_samples.push((is_success, sample));
+            }
+        }
+    }
+
+    endpoints
+}
+
+fn get_interface_data(
+    endpoint: &HarEndpoint,
+    project_id: u32,
+    cat_id: u32,
+    file_path: &str,
+) -> InterfaceData {
+    let title = format!("{} {}", endpoint.method, endpoint.path);
+    let req_params = endpoint
+        .path_params
+        .iter()
+        .map(|name| json!({ "name": name, "desc": "", "example": "" }))
+        .collect();
+    let req_body_other =
+        infer_schema_from_samples(&endpoint.request_samples).map(|schema| schema.to_string());
+    let req_body_form = get_param_list(&endpoint.form_samples)
+        .into_iter()
+        .map(|(name, required, _)| {
+            json!({ "name": name, "type": "text", "required": required, "desc": "" })
+        })
+        .collect();
+    let req_query = get_param_list(&endpoint.query_samples)
+        .into_iter()
+        .map(|(name, required, example)| {
+            json!({ "name": name, "required": required, "desc": "", "example": example })
+        })
+        .collect();
+
+    let res_body = infer_response_schema(&endpoint.response_samples)
+        .map(|schema| schema.to_string())
+        .unwrap_or_default();
+
+    InterfaceData {
+        _id: string_to_hash_id(&format!(
+            "{}#{} {}{}",
+            file_path, endpoint.method, endpoint.host, endpoint.path
+        )),
+        path: endpoint.path.clone(),
+        project_id,
+        title,
+        catid: cat_id,
+        req_body_type: Some(String::from(if req_body_other.is_some() {
+            "json"
+        } else {
+            "form"
+        })),
+        req_body_other,
+        req_query: Some(req_query),
+        req_params: Some(req_params),
+        req_body_form: Some(req_body_form),
+        req_headers: Some(vec![]),
+        res_body_type: Some(String::from("json")),
+        res_body: Some(res_body),
+        method: endpoint.method.clone(),
+        up_time: None,
+    }
+}
+
+// /api/user/123/order/550e8400-e29b-41d4-a716-446655440000 -> /api/user/{id}/order/{id2}
+fn normalize_path(raw_path: &str) -> (String, Vec<String>) {
+    let mut path_params = vec![];
+    let segments: Vec<_> = raw_path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            if is_path_param(segment) {
+                let name = match path_params.len() {
+                    0 => String::from("id"),
+                    len => format!("id{}", len + 1),
+                };
+                let segment = format!("{{{}}}", name);
+                path_params.push(name);
+                segment
+            } else {
+                segment.to_string()
+            }
+        })
+        .collect();
+
+    (format!("/{}", segments.join("/")), path_params)
+}
+
+fn is_path_param(segment: &str) -> bool {
+    let is_numeric = segment.chars().all(|c| c.is_ascii_digit());
+    let is_uuid = segment.len() == 36
+        && segment.char_indices().all(|(index, c)| match index {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        });
+
+    is_numeric || is_uuid
+}
+
+// 返回 (参数名, 是否每个样本都出现, 示例值)
+fn get_param_list(samples: &[Vec<HarParam>]) -> Vec<(String, &'static str, String)> {
+    let mut params: Vec<(String, &'static str, String)> = vec![];
+
+    for param in samples.iter().flatten() {
+        if params.iter().any(|(name, _, _)| *name == param.name) {
+            continue;
+        }
+        let required = samples
+            .iter()
+            .all(|sample| sample.iter().any(|p| p.name == param.name));
+        params.push((
+            param.name.clone(),
+            if required { "1" } else { "0" },
+            param.value.clone(),
+        ));
+    }
+
+    params
+}
+
+fn get_response_text(content: &HarContent) -> Option<String> {
+    let text = content.text.clone()?;
+
+    match content.encoding.as_deref() {
+        Some("base64") => STANDARD
+            .decode(text)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok()),
+        _ => Some(text),
+    }
+}
+
+fn parse_json(text: &str) -> Option<Value> {
+    serde_json::from_str(text).ok()
+}
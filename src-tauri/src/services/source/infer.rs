@@ -0,0 +1,96 @@
+use serde_json::{json, Map, Value};
+
+// 由多个 json 样本推断 json schema，样本之间的差异体现为非必填字段
+pub fn infer_schema_from_samples(samples: &[Value]) -> Option<Value> {
+    samples
+        .iter()
+        .map(infer_schema)
+        .reduce(|merged, schema| merge_schema(&merged, &schema))
+}
+
+// 响应样本为 (是否成功, 响应体)，优先使用成功的响应推断，避免错误响应混入
+pub fn infer_response_schema(samples: &[(bool, Value)]) -> Option<Value> {
+    let success_samples: Vec<_> = samples
+        .iter()
+        .filter(|(is_success, _)| *is_success)
+        .map(|(_, sample)| sample.clone())
+        .collect();
+
+    if success_samples.is_empty() {
+        let samples: Vec<_> = samples.iter().map(|(_, sample)| sample.clone()).collect();
+        infer_schema_from_samples(&samples)
+    } else {
+        infer_schema_from_samples(&success_samples)
+    }
+}
+
+// 由单个 json 样本推断 json schema，对象的全部字段视为必填
+pub fn infer_schema(value: &Value) -> Value {
+    match value {
+        Value::Null => json!({ "type": "any" }),
+        Value::Bool(_) => json!({ "type": "boolean" }),
+        Value::Number(number) if number.is_f64() => json!({ "type": "number" }),
+        Value::Number(_) => json!({ "type": "integer" }),
+        Value::String(_) => json!({ "type": "string" }),
+        Value::Array(items) => {
+            let items = infer_schema_from_samples(items).unwrap_or(json!({ "type": "any" }));
+            json!({ "type": "array", "items": items })
+        }
+        Value::Object(object) => {
+            let properties: Map<String, Value> = object
+                .iter()
+                .map(|(key, value)| (key.clone(), infer_schema(value)))
+                .collect();
+            let required: Vec<_> = object.keys().cloned().collect();
+            json!({ "type": "object", "properties": properties, "required": required })
+        }
+    }
+}
+
+// 合并两个 schema：字段取并集，必填取交集，类型冲突时为 any
+pub fn merge_schema(a: &Value, b: &Value) -> Value {
+    let type_a = a["type"].as_str().unwrap_or("any");
+    let type_b = b["type"].as_str().unwrap_or("any");
+
+    match (type_a, type_b) {
+        // null 样本不确定类型，以另一个样本为准
+        ("any", _) => b.clone(),
+        (_, "any") => a.clone(),
+        ("integer", "number") | ("number", "integer") => json!({ "type": "number" }),
+        ("array", "array") => {
+            json!({ "type": "array", "items": merge_schema(&a["items"], &b["items"]) })
+        }
+        ("object", "object") => {
+            let empty = Map::new();
+            let properties_a = a["properties"].as_object().unwrap_or(&empty);
+            let properties_b = b["properties"].as_object().unwrap_or(&empty);
+            let mut properties = properties_a.clone();
+
+            for (key, schema_b) in properties_b {
+                let schema = match properties_a.get(key) {
+                    Some(schema_a) => merge_schema(schema_a, schema_b),
+                    None => schema_b.clone(),
+                };
+                properties.insert(key.clone(), schema);
+            }
+
+            let required: Vec<_> = get_required(a)
+                .into_iter()
+                .filter(|key| get_required(b).contains(key))
+                .collect();
+
+            json!({ "type": "object", "properties": properties, "required": required })
+        }
+        (type_a, type_b) if type_a == type_b => a.clone(),
+        _ => json!({ "type": "any" }),
+    }
+}
+
+fn get_required(schema: &Value) -> Vec<String> {
+    schema["required"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|key| key.as_str().map(|key| key.to_string()))
+        .collect()
+}
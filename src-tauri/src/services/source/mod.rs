@@ -0,0 +1,239 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use tauri::{AppHandle, Manager, State};
+
+use crate::{
+    models::{
+        source::SourceProject,
+        yapi::{
+            category::{InterfaceDataItem, MenuCategory},
+            config::{ProjectSource, YapiConfig},
+            interface::{InterfaceData, InterfaceFetchParams},
+            project::YapiProjectBaseInfo,
+            web_response::Attempted,
+        },
+    },
+    services::{
+        log::log,
+        yapi::{
+            self,
+            cache::write_cache_entry,
+            config::{get_project_config, merge_source_menu_to_project_config},
+            history::record_history,
+        },
+    },
+};
+
+pub mod har;
+pub mod infer;
+pub mod openapi;
+pub mod postman;
+pub mod yapi_dump;
+
+// 获取接口详情，yapi 来源的接口同时写入缓存与历史
+pub async fn fetch_interface_detail(
+    fetch_interface_params: InterfaceFetchParams,
+    app_handle: &AppHandle,
+) -> Result<Attempted<InterfaceData>, io::Error> {
+    let source_path = fetch_interface_params.source_path.clone();
+    let is_yapi_source = matches!(
+        get_interface_source(
+            &get_project_config(&source_path)?,
+            &fetch_interface_params.token,
+            fetch_interface_params.interface_id,
+        ),
+        None | Some(ProjectSource::Yapi)
+    );
+    let detail = load_interface_detail(fetch_interface_params, app_handle).await?;
+
+    if is_yapi_source {
+        // 缓存供离线重新生成使用，写入失败不影响本次结果
+        if let Err(e) = write_cache_entry(&source_path, &detail.value) {
+            log(app_handle, format!("写入接口缓存失败：{}", e));
+        }
+        if let Err(e) = record_history(&source_path, &detail.value) {
+            log(app_handle, format!("记录接口历史失败：{}", e));
+        }
+    }
+
+    Ok(detail)
+}
+
+// 获取接口详情但不写入任何文件，按接口所属项目的来源分发，本地文件来源只尝试一次
+pub async fn load_interface_detail(
+    fetch_interface_params: InterfaceFetchParams,
+    app_handle: &AppHandle,
+) -> Result<Attempted<InterfaceData>, io::Error> {
+    let project_config = get_project_config(&fetch_interface_params.source_path)?;
+
+    let source = get_interface_source(
+        &project_config,
+        &fetch_interface_params.token,
+        fetch_interface_params.interface_id,
+    );
+    let (file_path, load_source_project): (_, SourceLoader) = match source {
+        Some(ProjectSource::OpenApi { file_path }) => (file_path, openapi::load_source_project),
+        Some(ProjectSource::YapiDump { file_path }) => (file_path, yapi_dump::load_source_project),
+        Some(ProjectSource::Har { file_path }) => (file_path, har::load_source_project),
+        Some(ProjectSource::Postman { file_path }) => (file_path, postman::load_source_project),
+        _ => {
+            return yapi::interface::fetch_interface_detail(fetch_interface_params, app_handle)
+                .await
+        }
+    };
+
+    let source_project_state: State<'_, SourceProjectState> = app_handle.state();
+    let source_project = source_project_state.load(
+        &fetch_interface_params.source_path,
+        &file_path,
+        load_source_project,
+    )?;
+    find_interface_data(&source_project, fetch_interface_params.interface_id)
+}
+
+type SourceLoader = fn(&str, &str) -> Result<SourceProject, io::Error>;
+
+// 解析过的来源文件，按文件修改时间判断是否需要重新解析，避免队列中每个接口都解析一次
+#[derive(Default)]
+pub struct SourceProjectState {
+    projects: Mutex<HashMap<PathBuf, (SystemTime, Arc<SourceProject>)>>,
+}
+
+impl SourceProjectState {
+    pub fn load(
+        &self,
+        source_path: &str,
+        file_path: &str,
+        load_source_project: SourceLoader,
+    ) -> Result<Arc<SourceProject>, io::Error> {
+        let full_path = get_source_file_path(source_path, file_path);
+        let modified = fs::metadata(&full_path)?.modified()?;
+        let mut projects = self.projects.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some((cached_modified, source_project)) = projects.get(&full_path) {
+            if *cached_modified == modified {
+                return Ok(source_project.clone());
+            }
+        }
+
+        let source_project = Arc::new(load_source_project(source_path, file_path)?);
+        projects.insert(full_path, (modified, source_project.clone()));
+        Ok(source_project)
+    }
+}
+
+// 查找接口所属项目的来源，同一 token 下可能有多个项目，以接口 id 区分
+pub fn get_interface_source(
+    project_config: &YapiConfig,
+    token: &str,
+    interface_id: u32,
+) -> Option<ProjectSource> {
+    let interface_id = interface_id.to_string();
+
+    project_config
+        .project_list
+        .iter()
+        .filter(|project| project.token == token)
+        .find(|project| {
+            project.categories.iter().any(|category| {
+                category
+                    .interfaces
+                    .iter()
+                    .any(|interface| interface.id == interface_id)
+            })
+        })
+        .and_then(|project| project.source.clone())
+}
+
+// 来源文件路径，相对路径基于代码库根目录
+pub fn get_source_file_path(source_path: &str, file_path: &str) -> PathBuf {
+    PathBuf::from(source_path).join(file_path)
+}
+
+// 把解析出的项目写入 yapi.json，沿用 yapi 项目的合并逻辑
+pub fn merge_source_project_to_project_config(
+    source_path: &str,
+    source_project: &SourceProject,
+    source: ProjectSource,
+) -> Result<(), io::Error> {
+    check_duplicate_paths(source_project)?;
+
+    let project_base_info = YapiProjectBaseInfo {
+        _id: source_project.project_id,
+        name: source_project.name.clone(),
+    };
+    let menu: Vec<MenuCategory> = source_project
+        .categories
+        .iter()
+        .map(|category| MenuCategory {
+            _id: category.cat_id,
+            name: category.name.clone(),
+            list: category
+                .interfaces
+                .iter()
+                .map(|interface| InterfaceDataItem {
+                    _id: interface._id,
+                    catid: interface.catid,
+                    title: interface.title.clone(),
+                    path: interface.path.clone(),
+                    up_time: interface.up_time,
+                })
+                .collect(),
+        })
+        .collect();
+
+    merge_source_menu_to_project_config(source_path, &project_base_info, source, &menu)
+}
+
+// 生成的文件与类型名只由接口路径决定，同一路径的多个请求方法会互相覆盖
+fn check_duplicate_paths(source_project: &SourceProject) -> Result<(), io::Error> {
+    let mut methods: HashMap<&str, &str> = HashMap::new();
+
+    for interface in source_project
+        .categories
+        .iter()
+        .flat_map(|category| &category.interfaces)
+    {
+        match methods.get(interface.path.as_str()) {
+            Some(method) if *method != interface.method => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "接口路径 {} 同时存在 {} 与 {} 请求，生成的文件与类型名会冲突",
+                        interface.path, method, interface.method
+                    ),
+                ));
+            }
+            _ => {
+                methods.insert(&interface.path, &interface.method);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn find_interface_data(
+    source_project: &SourceProject,
+    interface_id: u32,
+) -> Result<Attempted<InterfaceData>, io::Error> {
+    source_project
+        .categories
+        .iter()
+        .flat_map(|category| &category.interfaces)
+        .find(|interface| interface._id == interface_id)
+        .map(|value| Attempted {
+            value: value.clone(),
+            attempts: 1,
+        })
+        .ok_or(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("来源文件中不存在接口 {}", interface_id),
+        ))
+}
@@ -0,0 +1,427 @@
+use std::{fs, io};
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    models::{
+        source::{SourceCategory, SourceProject},
+        yapi::interface::InterfaceData,
+    },
+    services::conversion::string_to_hash_id,
+};
+
+use super::get_source_file_path;
+
+const METHODS: [&str; 7] = ["get", "post", "put", "delete", "patch", "head", "options"];
+
+// 未声明 tags 的接口归入该分类
+const DEFAULT_TAG: &str = "default";
+
+#[derive(PartialEq)]
+enum SpecVersion {
+    Swagger2,
+    OpenApi3,
+}
+
+// 读取 OpenAPI 2/3 文件（json 或 yaml）并转换为项目结构
+pub fn load_source_project(source_path: &str, file_path: &str) -> Result<SourceProject, io::Error> {
+    let contents = fs::read_to_string(get_source_file_path(source_path, file_path))?;
+    let document = parse_document(&contents)?;
+
+    get_source_project(&document, file_path)
+}
+
+fn parse_document(contents: &str) -> Result<Value, io::Error> {
+    match serde_json::from_str(contents) {
+        Ok(document) => Ok(document),
+        Err(_) => serde_yaml::from_str(contents).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("解析 OpenAPI 文件失败: {}", e),
+            )
+        }),
+    }
+}
+
+// tags 转换为分类，operation 转换为接口，id 由文件路径与接口签名生成以保证重复导入时不变
+pub fn get_source_project(document: &Value, file_path: &str) -> Result<SourceProject, io::Error> {
+    let version = get_spec_version(document)?;
+    let project_id = string_to_hash_id(file_path);
+    let name = document["info"]["title"]
+        .as_str()
+        .filter(|title| !title.is_empty())
+        .unwrap_or(file_path)
+        .to_string();
+
+    let mut categories: Vec<SourceCategory> = vec![];
+    for tag in document["tags"].as_array().into_iter().flatten() {
+        if let Some(tag_name) = tag["name"].as_str() {
+            get_category(&mut categories, file_path, tag_name);
+        }
+    }
+
+    let empty_paths = Map::new();
+    let paths = document["paths"].as_object().unwrap_or(&empty_paths);
+
+    for (path, path_item) in paths {
+        let path_item = resolve_ref(document, path_item);
+        let path_parameters = get_array(&path_item, "parameters");
+
+        for method in METHODS {
+            let operation = match path_item.get(method) {
+                Some(operation) if operation.is_object() => operation,
+                _ => continue,
+            };
+            let tag_name = operation["tags"][0].as_str().unwrap_or(DEFAULT_TAG);
+            let category = get_category(&mut categories, file_path, tag_name);
+            let interface_data = get_interface_data(
+                document,
+                &version,
+                (project_id, category.cat_id),
+                path,
+                method,
+                &path_parameters,
+                operation,
+                file_path,
+            );
+
+            category.interfaces.push(interface_data);
+        }
+    }
+
+    categories.retain(|category| !category.interfaces.is_empty());
+
+    Ok(SourceProject {
+        project_id,
+        name,
+        categories,
+    })
+}
+
+fn get_spec_version(document: &Value) -> Result<SpecVersion, io::Error> {
+    if document["openapi"]
+        .as_str()
+        .map_or(false, |version| version.starts_with('3'))
+    {
+        Ok(SpecVersion::OpenApi3)
+    } else if document["swagger"].as_str() == Some("2.0") {
+        Ok(SpecVersion::Swagger2)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "仅支持 Swagger 2.0 与 OpenAPI 3.x 文件",
+        ))
+    }
+}
+
+fn get_category<'a>(
+    categories: &'a mut Vec<SourceCategory>,
+    file_path: &str,
+    tag_name: &str,
+) -> &'a mut SourceCategory {
+    let index = match categories.iter().position(|c| c.name == tag_name) {
+        Some(index) => index,
+        None => {
+            categories.push(SourceCategory {
+                cat_id: string_to_hash_id(&format!("{}#{}", file_path, tag_name)),
+                name: tag_name.to_string(),
+                interfaces: vec![],
+            });
+            categories.len() - 1
+        }
+    };
+
+    &mut categories[index]
+}
+
+#[allow(clippy::too_many_arguments)]
+fn get_interface_data(
+    document: &Value,
+    version: &SpecVersion,
+    (project_id, cat_id): (u32, u32),
+    path: &str,
+    method: &str,
+    path_parameters: &[Value],
+    operation: &Value,
+    file_path: &str,
+) -> InterfaceData {
+    let method = method.to_uppercase();
+    let title = ["summary", "operationId"]
+        .iter()
+        .filter_map(|key| operation[*key].as_str())
+        .find(|title| !title.is_empty())
+        .map(|title| title.to_string())
+        .unwrap_or(format!("{} {}", method, path));
+
+    let mut req_query = vec![];
+    let mut req_params = vec![];
+    let mut req_headers = vec![];
+    let mut req_body_form = vec![];
+    let mut req_body_other = None;
+
+    for parameter in get_parameters(document, path_parameters, operation) {
+        let name = parameter["name"].as_str().unwrap_or_default();
+        let required = if parameter["required"].as_bool().unwrap_or(false) {
+            "1"
+        } else {
+            "0"
+        };
+        let desc = parameter["description"].as_str().unwrap_or_default();
+        let example = get_parameter_example(&parameter);
+
+        match parameter["in"].as_str().unwrap_or_default() {
+            "query" => req_query.push(json!({
+                "name": name, "required": required, "desc": desc, "example": example
+            })),
+            "path" => req_params.push(json!({ "name": name, "desc": desc, "example": example })),
+            "header" => req_headers.push(json!({
+                "name": name, "value": example, "required": required, "desc": desc
+            })),
+            // Swagger 2 的请求体
+            "body" => {
+                req_body_other = Some(get_schema_string(document, &parameter["schema"]));
+            }
+            "formData" => req_body_form.push(get_form_field(
+                name,
+                parameter["type"].as_str() == Some("file"),
+                required,
+                desc,
+            )),
+            _ => {}
+        }
+    }
+
+    if *version == SpecVersion::OpenApi3 {
+        let request_body = resolve_ref(document, &operation["requestBody"]);
+        let content = &request_body["content"];
+
+        if let Some(schema) = get_json_content_schema(content) {
+            req_body_other = Some(get_schema_string(document, schema));
+        } else if let Some(schema) = ["application/x-www-form-urlencoded", "multipart/form-data"]
+            .iter()
+            .find_map(|content_type| content[*content_type].get("schema"))
+        {
+            let schema = normalize_schema(document, schema, &mut vec![]);
+            let required = get_array(&schema, "required");
+
+            for (name, property) in schema["properties"].as_object().into_iter().flatten() {
+                let is_required = required.iter().any(|r| r.as_str() == Some(name.as_str()));
+                req_body_form.push(get_form_field(
+                    name,
+                    property["format"].as_str() == Some("binary"),
+                    if is_required { "1" } else { "0" },
+                    property["description"].as_str().unwrap_or_default(),
+                ));
+            }
+        }
+    }
+
+    // 无请求体时按 form 处理，由 resolver 读取 query 参数
+    let req_body_type = if req_body_other.is_some() {
+        "json"
+    } else {
+        "form"
+    };
+    let res_body = get_response_schema(operation, version)
+        .map(|schema| get_schema_string(document, schema));
+
+    InterfaceData {
+        _id: string_to_hash_id(&format!("{}#{} {}", file_path, method, path)),
+        path: path.to_string(),
+        project_id,
+        title,
+        catid: cat_id,
+        req_body_other,
+        req_query: Some(req_query),
+        req_params: Some(req_params),
+        req_body_form: Some(req_body_form),
+        req_body_type: Some(req_body_type.to_string()),
+        req_headers: Some(req_headers),
+        res_body_type: Some(String::from("json")),
+        res_body: Some(res_body.unwrap_or_default()),
+        method,
+        up_time: None,
+    }
+}
+
+// 合并路径级与接口级的参数，同名同位置时接口级优先
+fn get_parameters(document: &Value, path_parameters: &[Value], operation: &Value) -> Vec<Value> {
+    let mut parameters: Vec<Value> = vec![];
+
+    for parameter in path_parameters.iter().chain(get_array(operation, "parameters").iter()) {
+        let parameter = resolve_ref(document, parameter);
+        parameters.retain(|p| !(p["name"] == parameter["name"] && p["in"] == parameter["in"]));
+        parameters.push(parameter);
+    }
+
+    parameters
+}
+
+fn get_parameter_example(parameter: &Value) -> String {
+    let example = [&parameter["example"], &parameter["schema"]["example"], &parameter["default"]]
+        .into_iter()
+        .find(|example| !example.is_null());
+
+    match example {
+        Some(Value::String(example)) => example.clone(),
+        Some(example) => example.to_string(),
+        None => String::new(),
+    }
+}
+
+fn get_form_field(name: &str, is_file: bool, required: &str, desc: &str) -> Value {
+    json!({
+        "name": name,
+        "type": if is_file { "file" } else { "text" },
+        "required": required,
+        "desc": desc
+    })
+}
+
+// 取第一个成功的响应，其次是 default
+fn get_response_schema<'a>(operation: &'a Value, version: &SpecVersion) -> Option<&'a Value> {
+    let responses = operation["responses"].as_object()?;
+    let response = ["200", "201"]
+        .iter()
+        .find_map(|status| responses.get(*status))
+        .or_else(|| {
+            responses
+                .iter()
+                .find(|(status, _)| status.starts_with('2'))
+                .map(|(_, response)| response)
+        })
+        .or_else(|| responses.get("default"))?;
+
+    match version {
+        SpecVersion::Swagger2 => response.get("schema"),
+        SpecVersion::OpenApi3 => get_json_content_schema(&response["content"]),
+    }
+}
+
+fn get_json_content_schema(content: &Value) -> Option<&Value> {
+    content
+        .as_object()?
+        .iter()
+        .find(|(content_type, _)| content_type.contains("json"))
+        .and_then(|(_, media_type)| media_type.get("schema"))
+}
+
+fn get_schema_string(document: &Value, schema: &Value) -> String {
+    normalize_schema(document, schema, &mut vec![]).to_string()
+}
+
+// 转换为 yapi 使用的 json schema：展开 $ref、合并 allOf，
+// 对象总是带有 properties，数组总是带有 items，resolver 依赖这两个字段
+fn normalize_schema(document: &Value, schema: &Value, ref_stack: &mut Vec<String>) -> Value {
+    if let Some(reference) = schema["$ref"].as_str() {
+        // 循环引用处展开为空对象
+        if ref_stack.iter().any(|r| r == reference) {
+            return json!({ "type": "object", "properties": {} });
+        }
+        let target = match get_ref_target(document, reference) {
+            Some(target) => target,
+            None => return json!({ "type": "object", "properties": {} }),
+        };
+
+        ref_stack.push(reference.to_string());
+        let mut res = normalize_schema(document, target, ref_stack);
+        ref_stack.pop();
+
+        if let Some(description) = schema.get("description") {
+            res["description"] = description.clone();
+        }
+        return res;
+    }
+
+    if let Some(all_of) = schema["allOf"].as_array() {
+        let mut properties = Map::new();
+        let mut required = vec![];
+
+        for sub_schema in all_of {
+            let sub_schema = normalize_schema(document, sub_schema, ref_stack);
+            for (name, property) in sub_schema["properties"].as_object().into_iter().flatten() {
+                properties.insert(name.clone(), property.clone());
+            }
+            required.extend(get_array(&sub_schema, "required"));
+        }
+
+        let mut res = json!({ "type": "object", "properties": properties, "required": required });
+        copy_keys(schema, &mut res, &["description"]);
+        return res;
+    }
+
+    // 联合类型取第一个分支
+    if let Some(first) = ["oneOf", "anyOf"]
+        .iter()
+        .find_map(|key| schema[*key].as_array().and_then(|list| list.first()))
+    {
+        let mut res = normalize_schema(document, first, ref_stack);
+        copy_keys(schema, &mut res, &["description"]);
+        return res;
+    }
+
+    let schema_type = match &schema["type"] {
+        Value::String(schema_type) => schema_type.clone(),
+        Value::Array(types) => types
+            .iter()
+            .filter_map(|t| t.as_str())
+            .find(|t| *t != "null")
+            .unwrap_or("string")
+            .to_string(),
+        _ if schema.get("properties").is_some() => String::from("object"),
+        _ if schema.get("items").is_some() => String::from("array"),
+        _ => String::from("any"),
+    };
+
+    let mut res = json!({ "type": schema_type });
+    copy_keys(
+        schema,
+        &mut res,
+        &["description", "format", "enum", "default", "example", "title"],
+    );
+
+    match schema_type.as_str() {
+        "object" => {
+            let properties: Map<String, Value> = schema["properties"]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(name, property)| (name.clone(), normalize_schema(document, property, ref_stack)))
+                .collect();
+            res["properties"] = Value::Object(properties);
+            res["required"] = json!(get_array(schema, "required"));
+        }
+        "array" => {
+            res["items"] = normalize_schema(document, &schema["items"], ref_stack);
+        }
+        _ => {}
+    }
+
+    res
+}
+
+fn resolve_ref(document: &Value, value: &Value) -> Value {
+    match value["$ref"].as_str() {
+        Some(reference) => get_ref_target(document, reference)
+            .cloned()
+            .unwrap_or(Value::Null),
+        None => value.clone(),
+    }
+}
+
+// 仅支持文档内部引用：#/definitions/User、#/components/schemas/User
+fn get_ref_target<'a>(document: &'a Value, reference: &str) -> Option<&'a Value> {
+    document.pointer(reference.strip_prefix('#')?)
+}
+
+fn get_array(value: &Value, key: &str) -> Vec<Value> {
+    value[key].as_array().cloned().unwrap_or_default()
+}
+
+fn copy_keys(from: &Value, to: &mut Value, keys: &[&str]) {
+    for key in keys {
+        if let Some(value) = from.get(*key) {
+            to[*key] = value.clone();
+        }
+    }
+}
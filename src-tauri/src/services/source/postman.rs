@@ -0,0 +1,317 @@
+use std::{fs, io};
+
+use serde_json::{json, Value};
+
+use crate::{
+    models::{
+        source::{SourceCategory, SourceProject},
+        yapi::interface::InterfaceData,
+    },
+    services::conversion::string_to_hash_id,
+};
+
+use super::{
+    get_source_file_path,
+    infer::{infer_response_schema, infer_schema_from_samples},
+};
+
+// 同一方法与路径的请求合并为一个接口，样本用于推断类型
+struct PostmanEndpoint {
+    cat_id: u32,
+    title: String,
+    method: String,
+    path: String,
+    path_params: Vec<String>,
+    query: Vec<Value>,
+    form: Vec<Value>,
+    request_samples: Vec<Value>,
+    response_samples: Vec<(bool, Value)>,
+}
+
+// 读取 Postman v2.1 collection，文件夹对应分类，请求对应接口
+pub fn load_source_project(source_path: &str, file_path: &str) -> Result<SourceProject, io::Error> {
+    let contents = fs::read_to_string(get_source_file_path(source_path, file_path))?;
+    let collection: Value = serde_json::from_str(&contents).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("解析 Postman collection 失败: {}", e),
+        )
+    })?;
+
+    get_source_project(&collection, file_path)
+}
+
+fn get_source_project(collection: &Value, file_path: &str) -> Result<SourceProject, io::Error> {
+    let items = collection["item"].as_array().ok_or(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "仅支持 Postman v2.1 collection",
+    ))?;
+    let name = collection["info"]["name"]
+        .as_str()
+        .filter(|name| !name.is_empty())
+        .unwrap_or(file_path)
+        .to_string();
+    let project_id = string_to_hash_id(file_path);
+    let mut categories = vec![];
+    let mut endpoints = vec![];
+
+    // 根目录下的请求归入以 collection 命名的分类
+    collect_items(
+        items,
+        &name,
+        true,
+        file_path,
+        &mut categories,
+        &mut endpoints,
+    );
+
+    let categories = categories
+        .into_iter()
+        .map(|(cat_id, name)| SourceCategory {
+            cat_id,
+            name,
+            interfaces: endpoints
+                .iter()
+                .filter(|endpoint| endpoint.cat_id == cat_id)
+                .map(|endpoint| get_interface_data(endpoint, project_id, file_path))
+                .collect(),
+        })
+        .filter(|category| !category.interfaces.is_empty())
+        .collect();
+
+    Ok(SourceProject {
+        project_id,
+        name,
+        categories,
+    })
+}
+
+// 嵌套文件夹展开为 "父级 / 子级" 形式的分类
+fn collect_items(
+    items: &[Value],
+    category_name: &str,
+    is_root: bool,
+    file_path: &str,
+    categories: &mut Vec<(u32, String)>,
+    endpoints: &mut Vec<PostmanEndpoint>,
+) {
+    let cat_id = string_to_hash_id(&format!("{}#{}", file_path, category_name));
+    if !categories.iter().any(|(id, _)| *id == cat_id) {
+        categories.push((cat_id, category_name.to_string()));
+    }
+
+    for item in items {
+        let item_name = item["name"].as_str().unwrap_or_default();
+
+        if let Some(sub_items) = item["item"].as_array() {
+            let sub_category_name = if is_root {
+                item_name.to_string()
+            } else {
+                format!("{} / {}", category_name, item_name)
+            };
+            collect_items(
+                sub_items,
+                &sub_category_name,
+                false,
+                file_path,
+                categories,
+                endpoints,
+            );
+        } else if item["request"].is_object() || item["request"].is_string() {
+            add_endpoint(item, item_name, cat_id, endpoints);
+        }
+    }
+}
+
+fn add_endpoint(item: &Value, item_name: &str, cat_id: u32, endpoints: &mut Vec<PostmanEndpoint>) {
+    let request = &item["request"];
+    // request 可以直接是 url 字符串，此时为 GET 请求
+    let (method, url) = match request.as_str() {
+        Some(url) => (String::from("GET"), json!(url)),
+        None => (
+            request["method"].as_str().unwrap_or("GET").to_uppercase(),
+            request["url"].clone(),
+        ),
+    };
+    let (path, path_params) = get_path(&url);
+
+    let index = match endpoints
+        .iter()
+        .position(|e| e.method == method && e.path == path)
+    {
+        Some(index) => index,
+        None => {
+            endpoints.push(PostmanEndpoint {
+                cat_id,
+                title: if item_name.is_empty() {
+                    format!("{} {}", method, path)
+                } else {
+                    item_name.to_string()
+                },
+                method,
+                path,
+                path_params,
+                query: vec![],
+                form: vec![],
+                request_samples: vec![],
+                response_samples: vec![],
+            });
+            endpoints.len() - 1
+        }
+    };
+    let endpoint = &mut endpoints[index];
+
+    for query in url["query"].as_array().into_iter().flatten() {
+        let name = query["key"].as_str().unwrap_or_default();
+        if name.is_empty() || endpoint.query.iter().any(|q| q["name"] == name) {
+            continue;
+        }
+        let required = if query["disabled"].as_bool().unwrap_or(false) {
+            "0"
+        } else {
+            "1"
+        };
+        endpoint.query.push(json!({
+            "name": name,
+            "required": required,
+            "desc": get_description(&query["description"]),
+            "example": query["value"].as_str().unwrap_or_default()
+        }));
+    }
+
+    let body = &request["body"];
+    match body["mode"].as_str() {
+        Some("raw") => {
+            if let Some(sample) = body["raw"].as_str().and_then(parse_json) {
+                endpoint.request_samples.push(sample);
+            }
+        }
+        Some(mode @ ("urlencoded" | "formdata")) => {
+            for field in body[mode].as_array().into_iter().flatten() {
+                let name = field["key"].as_str().unwrap_or_default();
+                if name.is_empty() || endpoint.form.iter().any(|f| f["name"] == name) {
+                    continue;
+                }
+                let field_type = if field["type"].as_str() == Some("file") {
+                    "file"
+                } else {
+                    "text"
+                };
+                endpoint.form.push(json!({
+                    "name": name,
+                    "type": field_type,
+                    "required": if field["disabled"].as_bool().unwrap_or(false) { "0" } else { "1" },
+                    "desc": get_description(&field["description"])
+                }));
+            }
+        }
+        _ => {}
+    }
+
+    // 示例响应用于推断响应类型
+    for response in item["response"].as_array().into_iter().flatten() {
+        if let Some(sample) = response["body"].as_str().and_then(parse_json) {
+            let is_success = response["code"]
+                .as_u64()
+                .map_or(true, |code| (200..300).contains(&code));
+            endpoint.response_samples.push((is_success, sample));
+        }
+    }
+}
+
+fn get_interface_data(
+    endpoint: &PostmanEndpoint,
+    project_id: u32,
+    file_path: &str,
+) -> InterfaceData {
+    let req_body_other =
+        infer_schema_from_samples(&endpoint.request_samples).map(|schema| schema.to_string());
+    let req_params = endpoint
+        .path_params
+        .iter()
+        .map(|name| json!({ "name": name, "desc": "", "example": "" }))
+        .collect();
+
+    let res_body = infer_response_schema(&endpoint.response_samples)
+        .map(|schema| schema.to_string())
+        .unwrap_or_default();
+
+    InterfaceData {
+        _id: string_to_hash_id(&format!(
+            "{}#{} {}",
+            file_path, endpoint.method, endpoint.path
+        )),
+        path: endpoint.path.clone(),
+        project_id,
+        title: endpoint.title.clone(),
+        catid: endpoint.cat_id,
+        req_body_type: Some(String::from(if req_body_other.is_some() {
+            "json"
+        } else {
+            "form"
+        })),
+        req_body_other,
+        req_query: Some(endpoint.query.clone()),
+        req_params: Some(req_params),
+        req_body_form: Some(endpoint.form.clone()),
+        req_headers: Some(vec![]),
+        res_body_type: Some(String::from("json")),
+        res_body: Some(res_body),
+        method: endpoint.method.clone(),
+        up_time: None,
+    }
+}
+
+// url 可以是字符串或对象，:id 与 {{id}} 形式的路径段视为路径参数
+fn get_path(url: &Value) -> (String, Vec<String>) {
+    let segments: Vec<String> = match url["path"].as_array() {
+        Some(path) => path
+            .iter()
+            .filter_map(|segment| segment.as_str().map(|s| s.to_string()))
+            .collect(),
+        None => {
+            let raw = url.as_str().or(url["raw"].as_str()).unwrap_or_default();
+            let raw = raw.split(['?', '#']).next().unwrap_or_default();
+            let without_scheme = raw.split_once("://").map_or(raw, |(_, rest)| rest);
+            // 去掉域名或 {{baseUrl}} 之类的变量
+            match without_scheme.split_once('/') {
+                Some((_, path)) => path.split('/').map(|s| s.to_string()).collect(),
+                None => vec![],
+            }
+        }
+    };
+
+    let mut path_params = vec![];
+    let segments: Vec<_> = segments
+        .iter()
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            let param = segment.strip_prefix(':').or(segment
+                .strip_prefix("{{")
+                .and_then(|s| s.strip_suffix("}}")));
+
+            match param {
+                Some(name) => {
+                    path_params.push(name.to_string());
+                    format!("{{{}}}", name)
+                }
+                None => segment.clone(),
+            }
+        })
+        .collect();
+
+    (format!("/{}", segments.join("/")), path_params)
+}
+
+// description 可以是字符串或 { content } 对象
+fn get_description(description: &Value) -> String {
+    description
+        .as_str()
+        .or(description["content"].as_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn parse_json(text: &str) -> Option<Value> {
+    serde_json::from_str(text).ok()
+}
@@ -0,0 +1,131 @@
+use std::{fs, io, path::Path};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::{
+    models::{
+        source::{SourceCategory, SourceProject},
+        yapi::{category::CategoryMenuItem, interface::InterfaceData},
+    },
+    services::conversion::string_to_hash_id,
+};
+
+use super::get_source_file_path;
+
+// yapi 导出的 json 数据：分类数组，每个分类的 list 为完整的接口详情
+#[derive(Debug, Deserialize)]
+struct YapiDumpCategory {
+    name: String,
+    list: Vec<Value>,
+}
+
+// 读取 yapi 导出的 json 文件并转换为项目结构
+pub fn load_source_project(source_path: &str, file_path: &str) -> Result<SourceProject, io::Error> {
+    let contents = fs::read_to_string(get_source_file_path(source_path, file_path))?;
+    let dump: Vec<YapiDumpCategory> = serde_json::from_str(&contents).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("解析 yapi 导出文件失败: {}", e),
+        )
+    })?;
+
+    get_source_project(dump, file_path)
+}
+
+fn get_source_project(
+    dump: Vec<YapiDumpCategory>,
+    file_path: &str,
+) -> Result<SourceProject, io::Error> {
+    // 导出数据中保留了原项目 id，与线上项目一致时可以直接替换其来源
+    let project_id = dump
+        .iter()
+        .flat_map(|category| &category.list)
+        .find_map(|item| item["project_id"].as_u64())
+        .map(|project_id| project_id as u32)
+        .unwrap_or(string_to_hash_id(file_path));
+    let name = get_project_name(file_path);
+    let mut categories = vec![];
+
+    for category in dump {
+        let category_menu_item = get_category_menu_item(&category, file_path);
+        let interfaces = category
+            .list
+            .iter()
+            .map(|item| {
+                get_interface_data(item, project_id, category_menu_item._id, file_path)
+            })
+            .collect::<Result<Vec<_>, io::Error>>()?;
+
+        categories.push(SourceCategory {
+            cat_id: category_menu_item._id,
+            name: category_menu_item.name,
+            interfaces,
+        });
+    }
+
+    Ok(SourceProject {
+        project_id,
+        name,
+        categories,
+    })
+}
+
+// 导出数据中分类没有 id，取其下接口的 catid，空分类由文件路径与分类名生成
+fn get_category_menu_item(category: &YapiDumpCategory, file_path: &str) -> CategoryMenuItem {
+    let cat_id = category
+        .list
+        .iter()
+        .find_map(|item| item["catid"].as_u64())
+        .map(|cat_id| cat_id as u32)
+        .unwrap_or(string_to_hash_id(&format!("{}#{}", file_path, category.name)));
+
+    CategoryMenuItem {
+        _id: cat_id,
+        name: category.name.clone(),
+        interfaces: None,
+    }
+}
+
+fn get_interface_data(
+    item: &Value,
+    project_id: u32,
+    cat_id: u32,
+    file_path: &str,
+) -> Result<InterfaceData, io::Error> {
+    // 非对象的值按索引赋值会 panic
+    if !item.is_object() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("解析 yapi 导出的接口失败: 接口不是对象 {}", item),
+        ));
+    }
+    let mut item = item.clone();
+
+    if item["_id"].as_u64().is_none() {
+        let signature = format!(
+            "{}#{} {}",
+            file_path,
+            item["method"].as_str().unwrap_or_default(),
+            item["path"].as_str().unwrap_or_default()
+        );
+        item["_id"] = json!(string_to_hash_id(&signature));
+    }
+    item["project_id"] = json!(project_id);
+    item["catid"] = json!(cat_id);
+
+    serde_json::from_value(item).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("解析 yapi 导出的接口失败: {}", e),
+        )
+    })
+}
+
+// 导出文件中没有项目名，使用文件名
+fn get_project_name(file_path: &str) -> String {
+    Path::new(file_path)
+        .file_stem()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or(file_path.to_string())
+}
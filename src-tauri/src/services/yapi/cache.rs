@@ -0,0 +1,201 @@
+use std::{
+    fs, io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    models::yapi::{
+        cache::{CacheStats, CachedInterface},
+        interface::InterfaceData,
+    },
+    services::conversion::string_to_path_buf,
+};
+
+use super::config::get_project_config;
+
+// 缓存目录位于代码库根目录下，按 项目/接口 id 存放
+pub const CACHE_DIR_NAME: &str = ".yapi-cache";
+
+pub fn get_cache_dir(source_path: &str) -> PathBuf {
+    string_to_path_buf(source_path.to_string()).join(CACHE_DIR_NAME)
+}
+
+fn get_cache_entry_path(source_path: &str, project_id: &str, interface_id: &str) -> PathBuf {
+    get_cache_dir(source_path)
+        .join(project_id)
+        .join(format!("{}.json", interface_id))
+}
+
+// 写入接口详情，同一接口只保留最新的一份，保留上次生成时的详情
+pub fn write_cache_entry(source_path: &str, data: &InterfaceData) -> Result<(), io::Error> {
+    let generated = read_cache_entry(
+        source_path,
+        &data.project_id.to_string(),
+        &data._id.to_string(),
+    )
+    .ok()
+    .flatten()
+    .and_then(|cached_interface| cached_interface.generated);
+
+    write_cached_interface(source_path, data.clone(), generated)
+}
+
+// 文件写入后记录生成时的接口详情，作为之后比较变更的基准
+// 记录的是实际写入的详情，如从历史版本生成时为该版本，而非最新获取的详情
+// 没有缓存的接口（如文件导入）同时创建缓存
+pub fn mark_cache_generated(source_path: &str, data: &InterfaceData) -> Result<(), io::Error> {
+    let cached_data = read_cache_entry(
+        source_path,
+        &data.project_id.to_string(),
+        &data._id.to_string(),
+    )
+    .ok()
+    .flatten()
+    .map_or(data.clone(), |cached_interface| cached_interface.data);
+
+    write_cached_interface(source_path, cached_data, Some(data.clone()))
+}
+
+fn write_cached_interface(
+    source_path: &str,
+    data: InterfaceData,
+    generated: Option<InterfaceData>,
+) -> Result<(), io::Error> {
+    let file_path = get_cache_entry_path(
+        source_path,
+        &data.project_id.to_string(),
+        &data._id.to_string(),
+    );
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // 缓存不需要提交到代码库
+    let gitignore_path = get_cache_dir(source_path).join(".gitignore");
+    if !gitignore_path.exists() {
+        fs::write(gitignore_path, "*\n")?;
+    }
+
+    let cached_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let cached_interface = CachedInterface {
+        up_time: data.up_time,
+        cached_at,
+        data,
+        generated,
+    };
+
+    fs::write(file_path, serde_json::to_string(&cached_interface)?)
+}
+
+pub fn read_cache_entry(
+    source_path: &str,
+    project_id: &str,
+    interface_id: &str,
+) -> Result<Option<CachedInterface>, io::Error> {
+    let file_path = get_cache_entry_path(source_path, project_id, interface_id);
+    if !file_path.exists() {
+        return Ok(None);
+    }
+
+    Ok(Some(serde_json::from_str(&fs::read_to_string(file_path)?)?))
+}
+
+// 删除单个接口的缓存，返回是否存在
+pub fn remove_cache_entry(
+    source_path: &str,
+    project_id: &str,
+    interface_id: &str,
+) -> Result<bool, io::Error> {
+    match fs::remove_file(get_cache_entry_path(source_path, project_id, interface_id)) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+// 清除缓存：指定接口时只清除该接口，指定项目时清除整个项目，否则清除全部，返回删除的文件数
+pub fn invalidate_cache(
+    source_path: &str,
+    project_id: Option<&str>,
+    interface_id: Option<&str>,
+) -> Result<usize, io::Error> {
+    match (project_id, interface_id) {
+        (Some(project_id), Some(interface_id)) => {
+            Ok(remove_cache_entry(source_path, project_id, interface_id)? as usize)
+        }
+        (Some(project_id), None) => remove_cache_dir(get_cache_dir(source_path).join(project_id)),
+        (None, _) => remove_cache_dir(get_cache_dir(source_path)),
+    }
+}
+
+fn remove_cache_dir(dir: PathBuf) -> Result<usize, io::Error> {
+    if !dir.exists() {
+        return Ok(0);
+    }
+
+    let count = get_cache_files(&dir)?.len();
+    fs::remove_dir_all(dir)?;
+    Ok(count)
+}
+
+fn get_cache_files(dir: &PathBuf) -> Result<Vec<PathBuf>, io::Error> {
+    let mut files = vec![];
+    if !dir.exists() {
+        return Ok(files);
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(get_cache_files(&path)?);
+        } else if path.extension().map_or(false, |extension| extension == "json") {
+            files.push(path);
+        }
+    }
+
+    Ok(files)
+}
+
+pub fn get_cache_stats(source_path: &str) -> Result<CacheStats, io::Error> {
+    let cache_dir = get_cache_dir(source_path);
+    let files = get_cache_files(&cache_dir)?;
+    let mut cache_stats = CacheStats {
+        cache_dir: cache_dir.to_string_lossy().to_string(),
+        entries: files.len(),
+        ..Default::default()
+    };
+
+    for file in &files {
+        cache_stats.bytes += fs::metadata(file)?.len();
+    }
+
+    let project_config = get_project_config(source_path)?;
+    for project in &project_config.project_list {
+        for interface in project
+            .categories
+            .iter()
+            .flat_map(|category| &category.interfaces)
+        {
+            match read_cache_entry(source_path, &project.project_id, &interface.id) {
+                Ok(Some(cached_interface)) => {
+                    if is_cache_stale(&cached_interface, interface.up_time) {
+                        cache_stats.stale += 1;
+                    }
+                }
+                // 无法解析的缓存同样视为缺失
+                _ => cache_stats.missing += 1,
+            }
+        }
+    }
+
+    Ok(cache_stats)
+}
+
+pub fn is_cache_stale(cached_interface: &CachedInterface, up_time: Option<u64>) -> bool {
+    match (cached_interface.up_time, up_time) {
+        (Some(cached_up_time), Some(up_time)) => cached_up_time < up_time,
+        _ => false,
+    }
+}
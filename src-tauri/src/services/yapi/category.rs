@@ -1,38 +1,87 @@
 use std::io;
 
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
 
 use crate::{
-    models::yapi::{category::CategoryDataList, web_response::YapiResponse},
-    services::reqwest::{get_data, get_reqwest_client},
+    models::yapi::category::{CategoryDataList, CategoryFetchProgress},
+    services::{
+        global_config::get_global_config,
+        reqwest::{get_reqwest_client, get_retry_policy},
+    },
 };
 
-use super::config::get_project_config;
+use super::{client::get_yapi_data, config::get_yapi_server};
 
 const INTERFACE_LIST_CAT_API: &str = "api/interface/list_cat";
 
+pub const CATEGORY_FETCH_PROGRESS_EVENT: &str = "cat_interface_list_progress";
+
+const DEFAULT_PAGE_SIZE: u32 = 100;
+
+// 分页获取分类下的全部接口，yapi 返回的 count 为接口总数，total 为总页数
 pub async fn fetch_cat_interface_list(
     cat_id: u32,
     token: String,
     source_path: &str,
     app_handle: &AppHandle,
 ) -> Result<CategoryDataList, io::Error> {
-    let client = get_reqwest_client(&app_handle)?;
-    let mut project_config = get_project_config(source_path)?;
-    if !project_config.base_url.ends_with('/') {
-        project_config.base_url.push('/');
-    }
+    let server = get_yapi_server(source_path, &token, None)?;
+    let client = get_reqwest_client(app_handle, server.proxy.as_deref())?;
+    let retry_policy = get_retry_policy(app_handle)?;
+    let page_size = get_global_config(app_handle)?
+        .page_size
+        .filter(|page_size| *page_size > 0)
+        .unwrap_or(DEFAULT_PAGE_SIZE);
+
+    let mut res = CategoryDataList {
+        count: 0,
+        total: 0,
+        list: vec![],
+    };
+    let mut page = 1;
 
-    let url = format!(
-        "{}{}?token={}&catid={}&limit=1000",
-        project_config.base_url, INTERFACE_LIST_CAT_API, token, cat_id
-    );
-
-    match get_data::<YapiResponse<CategoryDataList>>(client, url.clone()).await {
-        Ok(res) => Ok(res.data),
-        Err(err) => Err(io::Error::new(
-            io::ErrorKind::Other,
-            format!("获取分类下的接口列表失败: {}, url:{}", err.to_string(), url),
-        )),
+    loop {
+        let url = format!(
+            "{}{}?token={}&catid={}&page={}&limit={}",
+            server.base_url, INTERFACE_LIST_CAT_API, token, cat_id, page, page_size
+        );
+        let data = get_yapi_data::<CategoryDataList>(
+            client.clone(),
+            url,
+            "获取分类下的接口列表",
+            &retry_policy,
+        )
+        .await?
+        .value;
+        let page_len = data.list.len();
+
+        res.count = data.count;
+        res.total = data.total;
+        // 翻页期间接口有增删时可能出现重复
+        for item in data.list {
+            if !res.list.iter().any(|i| i._id == item._id) {
+                res.list.push(item);
+            }
+        }
+
+        app_handle
+            .emit_all(
+                CATEGORY_FETCH_PROGRESS_EVENT,
+                CategoryFetchProgress {
+                    cat_id,
+                    page,
+                    total_pages: res.total,
+                    fetched: res.list.len() as u32,
+                    count: res.count,
+                },
+            )
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+
+        if page >= res.total || page_len == 0 || res.list.len() as u32 >= res.count {
+            break;
+        }
+        page += 1;
     }
+
+    Ok(res)
 }
@@ -0,0 +1,431 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use tauri::AppHandle;
+
+use crate::{
+    models::yapi::{
+        check::{CheckReport, FileDrift, FileDriftKind},
+        config::{YapiInterface, YapiProject},
+        interface::{InterfaceData, InterfaceFetchParams},
+    },
+    services::{
+        request::{
+            get_request_file_path, get_request_ts_string_from_files, get_type_relative_path,
+        },
+        source::load_interface_detail,
+    },
+};
+
+use super::{
+    cache::read_cache_entry,
+    config::{get_interface_lock_reason, get_project_config},
+    interface::{
+        find_go_file_path, get_dart_file_path, get_generated_file_paths, get_go_file_path,
+        get_interface_file_path, get_resolved_interface,
+    },
+    provenance::is_provenance_line,
+};
+
+// 差异前后保留的上下文行数
+const CONTEXT_LINES: usize = 3;
+
+enum LineOp<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+// 在内存中重新生成 types、request、dart 与 go 文件并与磁盘比较，不写入任何文件
+// use_cache 为 true 时优先使用缓存的接口详情
+pub async fn get_check_report(
+    source_path: &str,
+    use_cache: bool,
+    app_handle: &AppHandle,
+) -> Result<CheckReport, io::Error> {
+    let project_config = get_project_config(source_path)?;
+    let types_root_path = PathBuf::from(source_path).join(&project_config.types_path);
+    let mut report = CheckReport::default();
+    // 重新生成的 types、dart 与 go 文件：路径 -> 内容
+    let mut expected = BTreeMap::new();
+    // 锁定或检查失败的接口对应的文件，不参与比较
+    let mut skipped = BTreeSet::new();
+
+    for project in &project_config.project_list {
+        for interface in project
+            .categories
+            .iter()
+            .flat_map(|category| &category.interfaces)
+        {
+            // 未生成过的接口没有对应的文件，孤立接口已无法获取
+            let is_generated =
+                interface.generated_path.is_some() || interface.generated_up_time.is_some();
+            if !is_generated || interface.orphaned == Some(true) {
+                continue;
+            }
+
+            if let Some(reason) =
+                get_interface_lock_reason(&project_config, Some(&interface.id), None)
+            {
+                report
+                    .locked
+                    .push(format!("{}：{}", interface.name, reason));
+                skipped.extend(get_interface_files(source_path, interface)?);
+                continue;
+            }
+
+            let data =
+                match get_interface_data(source_path, project, interface, use_cache, app_handle)
+                    .await
+                {
+                    Ok(data) => data,
+                    Err(e) => {
+                        report.failed.push(format!("{}: {}", interface.name, e));
+                        skipped.extend(get_interface_files(source_path, interface)?);
+                        continue;
+                    }
+                };
+            let resolved_interface = match get_resolved_interface(data, &project_config) {
+                Ok(resolved_interface) => resolved_interface,
+                Err(e) => {
+                    report.failed.push(format!("{}: {}", interface.name, e));
+                    skipped.extend(get_interface_files(source_path, interface)?);
+                    continue;
+                }
+            };
+            let file_path =
+                get_interface_file_path(resolved_interface.interface.path.clone(), source_path)?
+                    .to_string_lossy()
+                    .to_string();
+
+            expected.insert(
+                PathBuf::from(format!("{}.ts", file_path)),
+                resolved_interface.ts_string,
+            );
+            if let Some(schema_string) = resolved_interface.schema_string {
+                expected.insert(
+                    PathBuf::from(format!("{}.schema.ts", file_path)),
+                    schema_string,
+                );
+            }
+            let path = resolved_interface.interface.path.clone();
+            if let Some(dart_string) = resolved_interface.dart_string {
+                expected.insert(get_dart_file_path(path.clone(), source_path)?, dart_string);
+            }
+            if let Some(go_string) = resolved_interface.go_string {
+                expected.insert(get_go_file_path(path, source_path, &go_string)?, go_string);
+            }
+        }
+    }
+
+    for (file_path, content) in &expected {
+        check_file(file_path, content, &mut report)?;
+    }
+
+    // 带有来源信息却不再生成的文件
+    let mut stamped_files = vec![];
+    get_stamped_files(&types_root_path, &mut stamped_files)?;
+    let mut other_stamped_files = vec![];
+    for other_path in [&project_config.dart_path, &project_config.go_path]
+        .into_iter()
+        .flatten()
+        .filter(|other_path| !other_path.is_empty())
+    {
+        get_stamped_files(
+            &PathBuf::from(source_path).join(other_path),
+            &mut other_stamped_files,
+        )?;
+    }
+    // dart 或 go 目录位于 types 目录下时文件会被重复查找
+    let all_stamped_files: BTreeSet<_> = stamped_files.iter().chain(&other_stamped_files).collect();
+    for file_path in all_stamped_files {
+        if !expected.contains_key(file_path) && !skipped.contains(file_path) {
+            report.drifts.push(FileDrift {
+                file_path: file_path.to_string_lossy().to_string(),
+                kind: FileDriftKind::Extra,
+                diff: None,
+            });
+        }
+    }
+
+    // request 文件按 types 目录生成，目录中手写的文件同样参与生成
+    let dir_paths: BTreeSet<_> = expected
+        .keys()
+        .filter(|file_path| is_ts_file(file_path))
+        .filter_map(|file_path| file_path.parent())
+        .map(Path::to_path_buf)
+        .collect();
+    for dir_path in &dir_paths {
+        let mut files: Vec<_> = expected
+            .iter()
+            .filter(|(file_path, _)| {
+                is_ts_file(file_path) && file_path.parent() == Some(dir_path.as_path())
+            })
+            .map(|(file_path, content)| (file_path.clone(), content.clone()))
+            .collect();
+        files.extend(
+            get_manual_files(dir_path, &stamped_files)?
+                .into_iter()
+                .filter(|(file_path, _)| !expected.contains_key(file_path)),
+        );
+        // 跳过的接口按磁盘上的现有文件参与生成
+        for file_path in skipped.iter().filter(|file_path| {
+            is_ts_file(file_path) && file_path.parent() == Some(dir_path.as_path())
+        }) {
+            if !expected.contains_key(file_path) && stamped_files.contains(file_path) {
+                files.push((file_path.clone(), fs::read_to_string(file_path)?));
+            }
+        }
+
+        let content = get_request_ts_string_from_files(
+            &project_config,
+            &get_type_relative_path(source_path, project_config.types_path.clone(), dir_path),
+            files,
+        );
+        if content.is_empty() {
+            continue;
+        }
+
+        check_file(
+            &get_request_file_path(source_path, &project_config, dir_path),
+            &content,
+            &mut report,
+        )?;
+    }
+
+    Ok(report)
+}
+
+// 接口上次生成时的文件，路径变更后按生成时的路径查找
+fn get_interface_files(
+    source_path: &str,
+    interface: &YapiInterface,
+) -> Result<Vec<PathBuf>, io::Error> {
+    match interface
+        .generated_path
+        .as_ref()
+        .or(interface.path.as_ref())
+    {
+        Some(path) => {
+            let mut file_paths = get_generated_file_paths(path, source_path)?;
+            file_paths.extend(find_go_file_path(path, source_path)?);
+            Ok(file_paths)
+        }
+        None => Ok(vec![]),
+    }
+}
+
+// dart 与 go 文件所在目录可能与 types 目录相同，request 文件只由 ts 文件生成
+fn is_ts_file(file_path: &Path) -> bool {
+    file_path
+        .extension()
+        .map_or(false, |extension| extension == "ts")
+}
+
+async fn get_interface_data(
+    source_path: &str,
+    project: &YapiProject,
+    interface: &YapiInterface,
+    use_cache: bool,
+    app_handle: &AppHandle,
+) -> Result<InterfaceData, io::Error> {
+    if use_cache {
+        if let Some(cached) = read_cache_entry(source_path, &project.project_id, &interface.id)? {
+            return Ok(cached.data);
+        }
+    }
+
+    let interface_id = interface.id.parse::<u32>().map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("非法的接口 id {}", interface.id),
+        )
+    })?;
+    let fetch_interface_params = InterfaceFetchParams {
+        interface_id,
+        token: project.token.clone(),
+        source_path: source_path.to_string(),
+    };
+
+    Ok(load_interface_detail(fetch_interface_params, app_handle)
+        .await?
+        .value)
+}
+
+fn check_file(file_path: &Path, content: &str, report: &mut CheckReport) -> Result<(), io::Error> {
+    report.checked += 1;
+
+    if !file_path.exists() {
+        report.drifts.push(FileDrift {
+            file_path: file_path.to_string_lossy().to_string(),
+            kind: FileDriftKind::Missing,
+            diff: None,
+        });
+        return Ok(());
+    }
+
+    let disk_content = fs::read_to_string(file_path)?;
+    if disk_content != content {
+        report.drifts.push(FileDrift {
+            file_path: file_path.to_string_lossy().to_string(),
+            kind: FileDriftKind::Stale,
+            diff: Some(get_line_diff(&disk_content, content)),
+        });
+    }
+
+    Ok(())
+}
+
+// 递归查找首行带有来源信息的文件
+fn get_stamped_files(dir_path: &Path, files: &mut Vec<PathBuf>) -> Result<(), io::Error> {
+    if !dir_path.exists() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let file_path = entry.path();
+
+        if entry.file_type()?.is_dir() {
+            get_stamped_files(&file_path, files)?;
+        } else if let Ok(content) = fs::read_to_string(&file_path) {
+            if content.lines().next().map_or(false, is_provenance_line) {
+                files.push(file_path);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// 目录下手写的文件，即不带来源信息的文件
+fn get_manual_files(
+    dir_path: &Path,
+    stamped_files: &[PathBuf],
+) -> Result<Vec<(PathBuf, String)>, io::Error> {
+    let mut files = vec![];
+    if !dir_path.exists() {
+        return Ok(files);
+    }
+
+    for entry in fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let file_path = entry.path();
+        if !entry.file_type()?.is_file() || stamped_files.contains(&file_path) {
+            continue;
+        }
+        if let Ok(content) = fs::read_to_string(&file_path) {
+            files.push((file_path, content));
+        }
+    }
+
+    Ok(files)
+}
+
+// 按行比较，返回 unified diff 格式的差异
+pub fn get_line_diff(before: &str, after: &str) -> String {
+    let before_lines: Vec<_> = before.lines().collect();
+    let after_lines: Vec<_> = after.lines().collect();
+    let ops = get_line_ops(&before_lines, &after_lines);
+    let mut res_string = String::new();
+    let mut index = 0;
+
+    while let Some(offset) = ops[index..]
+        .iter()
+        .position(|op| !matches!(op, LineOp::Equal(_)))
+    {
+        let start = (index + offset).saturating_sub(CONTEXT_LINES).max(index);
+
+        // 两处变更间隔不超过两倍上下文时合并为一段
+        let mut last_change = index + offset;
+        let mut cursor = last_change + 1;
+        while cursor < ops.len() && cursor - last_change <= CONTEXT_LINES * 2 {
+            if !matches!(ops[cursor], LineOp::Equal(_)) {
+                last_change = cursor;
+            }
+            cursor += 1;
+        }
+        let end = (last_change + 1 + CONTEXT_LINES).min(ops.len());
+
+        let before_start = ops[..start]
+            .iter()
+            .filter(|op| !matches!(op, LineOp::Insert(_)))
+            .count();
+        let after_start = ops[..start]
+            .iter()
+            .filter(|op| !matches!(op, LineOp::Delete(_)))
+            .count();
+        let hunk = &ops[start..end];
+        let before_count = hunk
+            .iter()
+            .filter(|op| !matches!(op, LineOp::Insert(_)))
+            .count();
+        let after_count = hunk
+            .iter()
+            .filter(|op| !matches!(op, LineOp::Delete(_)))
+            .count();
+
+        res_string += &format!(
+            "@@ -{},{} +{},{} @@\n",
+            before_start + 1,
+            before_count,
+            after_start + 1,
+            after_count
+        );
+        for op in hunk {
+            res_string += &match op {
+                LineOp::Equal(line) => format!(" {}\n", line),
+                LineOp::Delete(line) => format!("-{}\n", line),
+                LineOp::Insert(line) => format!("+{}\n", line),
+            };
+        }
+
+        index = end;
+    }
+
+    // 逐行内容相同时只可能是换行符不同
+    if res_string.is_empty() && before != after {
+        res_string = String::from("换行符不同\n");
+    }
+
+    res_string
+}
+
+// 基于最长公共子序列得到逐行的编辑操作
+fn get_line_ops<'a>(before: &[&'a str], after: &[&'a str]) -> Vec<LineOp<'a>> {
+    let (n, m) = (before.len(), after.len());
+    // lengths[i][j]：before[i..] 与 after[j..] 的最长公共子序列长度
+    let mut lengths = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if before[i] == after[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if before[i] == after[j] {
+            ops.push(LineOp::Equal(before[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            ops.push(LineOp::Delete(before[i]));
+            i += 1;
+        } else {
+            ops.push(LineOp::Insert(after[j]));
+            j += 1;
+        }
+    }
+    ops.extend(before[i..].iter().map(|line| LineOp::Delete(line)));
+    ops.extend(after[j..].iter().map(|line| LineOp::Insert(line)));
+
+    ops
+}
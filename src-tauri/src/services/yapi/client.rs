@@ -0,0 +1,156 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tokio::time::sleep;
+
+use crate::models::{
+    global_config::RetryPolicy,
+    yapi::{
+        error::{YapiError, YapiErrorKind},
+        web_response::{Attempted, YapiResponse},
+    },
+};
+
+// 请求 yapi 开放接口，按重试策略重试可恢复的错误
+pub async fn get_yapi_data<T>(
+    client: Client,
+    url: String,
+    action: &str,
+    retry_policy: &RetryPolicy,
+) -> Result<Attempted<T>, YapiError>
+where
+    T: DeserializeOwned,
+{
+    let max_attempts = retry_policy.max_attempts.max(1);
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
+
+        match request_yapi_data(&client, &url, action).await {
+            Ok(value) => return Ok(Attempted { value, attempts }),
+            Err(mut error) => {
+                if attempts >= max_attempts || !error.is_retryable() {
+                    error.attempts = attempts;
+                    return Err(error);
+                }
+            }
+        }
+
+        sleep(get_retry_delay(retry_policy, attempts)).await;
+    }
+}
+
+// errcode 非 0 时返回对应的错误而不是解析 data
+async fn request_yapi_data<T>(client: &Client, url: &str, action: &str) -> Result<T, YapiError>
+where
+    T: DeserializeOwned,
+{
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| get_request_error(e, action))?;
+    let status = response.status();
+    let text = response.text().await.map_err(|e| {
+        YapiError::new(
+            YapiErrorKind::Network,
+            action,
+            Some(status.as_u16()),
+            e.to_string(),
+        )
+    })?;
+
+    let value: Value = match serde_json::from_str(&text) {
+        Ok(value) => value,
+        Err(e) => {
+            // 网关等返回的非 json 内容
+            let (kind, errmsg) = if status.is_success() {
+                (YapiErrorKind::Decode, e.to_string())
+            } else {
+                (YapiErrorKind::Http, get_text_summary(&text))
+            };
+            return Err(YapiError::new(kind, action, Some(status.as_u16()), errmsg));
+        }
+    };
+
+    // 5xx 与 429 即使带有 errcode 也按 http 错误处理，以便重试
+    if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+        let errmsg = value["errmsg"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| get_text_summary(&text));
+        return Err(YapiError::new(
+            YapiErrorKind::Http,
+            action,
+            Some(status.as_u16()),
+            errmsg,
+        ));
+    }
+
+    let errcode = value["errcode"].as_i64().unwrap_or(0);
+    if errcode != 0 {
+        let errmsg = value["errmsg"].as_str().unwrap_or_default().to_string();
+        return Err(YapiError::from_errcode(
+            action,
+            status.as_u16(),
+            errcode,
+            errmsg,
+        ));
+    }
+    if !status.is_success() {
+        return Err(YapiError::new(
+            YapiErrorKind::Http,
+            action,
+            Some(status.as_u16()),
+            get_text_summary(&text),
+        ));
+    }
+
+    match serde_json::from_value::<YapiResponse<T>>(value) {
+        Ok(res) => Ok(res.data),
+        Err(e) => Err(YapiError::new(
+            YapiErrorKind::Decode,
+            action,
+            Some(status.as_u16()),
+            e.to_string(),
+        )),
+    }
+}
+
+fn get_request_error(error: reqwest::Error, action: &str) -> YapiError {
+    let kind = if error.is_connect() || error.is_timeout() {
+        YapiErrorKind::Network
+    } else {
+        YapiErrorKind::Request
+    };
+    let status = error.status().map(|status| status.as_u16());
+
+    YapiError::new(kind, action, status, error.to_string())
+}
+
+// 指数退避：base * 2^(n-1)，不超过上限，再加上随机抖动
+fn get_retry_delay(retry_policy: &RetryPolicy, attempts: u32) -> Duration {
+    let exponent = attempts.saturating_sub(1).min(16);
+    let delay = retry_policy
+        .base_delay_ms
+        .saturating_mul(1 << exponent)
+        .min(retry_policy.max_delay_ms);
+    let jitter = if retry_policy.jitter_ms == 0 {
+        0
+    } else {
+        // 不引入随机数依赖，使用当前时间的纳秒部分
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.subsec_nanos() as u64);
+        nanos % (retry_policy.jitter_ms + 1)
+    };
+
+    Duration::from_millis(delay + jitter)
+}
+
+fn get_text_summary(text: &str) -> String {
+    text.chars().take(200).collect()
+}
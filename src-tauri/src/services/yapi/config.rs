@@ -8,13 +8,20 @@ use serde_json::from_str;
 
 use crate::{
     models::yapi::{
-        category::{CategoryMenuItem, InterfaceDataItem},
-        config::{YapiCategory, YapiConfig, YapiInterface, YapiProject},
-        project::YapiProjectBaseInfo,
+        category::{CategoryMenuItem, InterfaceDataItem, MenuCategory},
+        config::{
+            ProjectSource, YapiCategory, YapiConfig, YapiInterface, YapiProject, YapiServer,
+        },
+        project::{
+            InterfaceChange, InterfaceMove, InterfaceRename, ProjectDiscoveryReport,
+            RemoteInterfaceReport, YapiProjectBaseInfo,
+        },
     },
     services::conversion::string_to_path_buf,
 };
 
+use super::cache::remove_cache_entry;
+
 pub const PROJECT_CONFIG_NAME: &str = "yapi.json";
 
 pub fn init_project_config(source_path: String) -> Result<(), io::Error> {
@@ -67,25 +74,143 @@ pub fn merge_yapi_project_to_project_config(
     source_path: &str,
     yapi_project_base_info: &YapiProjectBaseInfo,
     token: &str,
+    source: Option<ProjectSource>,
+    base_url: Option<&str>,
 ) -> Result<(), io::Error> {
     let mut yapi_config = get_project_config(source_path)?;
-    if !yapi_config
+    merge_yapi_project(
+        &mut yapi_config,
+        yapi_project_base_info,
+        token,
+        source,
+        base_url,
+    )?;
+
+    write_project_config(source_path, yapi_config)?;
+    Ok(())
+}
+
+fn merge_yapi_project(
+    yapi_config: &mut YapiConfig,
+    yapi_project_base_info: &YapiProjectBaseInfo,
+    token: &str,
+    source: Option<ProjectSource>,
+    base_url: Option<&str>,
+) -> Result<(), io::Error> {
+    let default_base_url = yapi_config.base_url.clone();
+    let project = yapi_config
         .project_list
-        .iter()
-        .any(|project| project.project_id == format!("{}", yapi_project_base_info._id))
-    {
+        .iter_mut()
+        .find(|project| project.project_id == format!("{}", yapi_project_base_info._id));
+
+    if let Some(project) = project {
+        // 项目按 project_id 区分，不同 yapi 上 id 相同的项目无法同时添加
+        let project_base_url = project.base_url.as_deref();
+        if source.is_none() && !is_same_base_url(project_base_url, base_url, &default_base_url) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!(
+                    "项目 {} 已存在于 {}，无法再添加其他 yapi 上 id 相同的项目",
+                    project.project_id,
+                    project_base_url
+                        .filter(|base_url| !base_url.is_empty())
+                        .unwrap_or(&default_base_url)
+                ),
+            ));
+        }
+        // 已存在的项目只更新来源，如把 yapi 项目指向导出的文件
+        if source.is_some() {
+            project.source = source;
+        }
+    } else {
         yapi_config.project_list.push(YapiProject {
             token: token.to_string(),
             project_id: format!("{}", yapi_project_base_info._id),
             project_name: Some(yapi_project_base_info.name.clone()),
             categories: vec![],
+            source,
+            base_url: None,
+            proxy: None,
+            lock: None,
         })
     };
 
+    Ok(())
+}
+
+// 两个项目的 yapi 地址是否相同，为空时使用代码库的 base_url
+fn is_same_base_url(a: Option<&str>, b: Option<&str>, default_base_url: &str) -> bool {
+    let get_base_url = |base_url: Option<&str>| {
+        base_url
+            .filter(|base_url| !base_url.is_empty())
+            .unwrap_or(default_base_url)
+            .trim_end_matches('/')
+            .to_string()
+    };
+
+    get_base_url(a) == get_base_url(b)
+}
+
+// 设置项目的 yapi 地址与代理，传入空字符串表示使用默认值
+pub fn update_project_server(
+    source_path: &str,
+    project_id: &str,
+    base_url: Option<String>,
+    proxy: Option<String>,
+) -> Result<(), io::Error> {
+    let mut yapi_config = get_project_config(source_path)?;
+    let project = yapi_config
+        .project_list
+        .iter_mut()
+        .find(|project| project.project_id == project_id)
+        .ok_or(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("项目 {} 不存在", project_id),
+        ))?;
+
+    if let Some(base_url) = base_url {
+        project.base_url = Some(base_url).filter(|base_url| !base_url.is_empty());
+    }
+    if let Some(proxy) = proxy {
+        project.proxy = Some(proxy).filter(|proxy| !proxy.is_empty());
+    }
+
     write_project_config(source_path, yapi_config)?;
     Ok(())
 }
 
+// 项目的 yapi 地址与代理，有 project_id 时按 project_id 查找，否则按 token 查找
+// 项目未配置时使用代码库的 base_url 与全局代理
+pub fn get_yapi_server(
+    source_path: &str,
+    token: &str,
+    project_id: Option<&str>,
+) -> Result<YapiServer, io::Error> {
+    let project_config = get_project_config(source_path)?;
+    let project = project_config
+        .project_list
+        .iter()
+        .find(|project| match project_id {
+            Some(project_id) => project.project_id == project_id,
+            None => project.token == token,
+        });
+
+    let mut base_url = project
+        .and_then(|project| project.base_url.clone())
+        .filter(|base_url| !base_url.is_empty())
+        .unwrap_or(project_config.base_url);
+    if !base_url.ends_with('/') {
+        base_url.push('/');
+    }
+
+    Ok(YapiServer {
+        base_url,
+        proxy: project
+            .and_then(|project| project.proxy.clone())
+            .filter(|proxy| !proxy.is_empty()),
+    })
+}
+
 pub fn merge_category_to_project_config(
     category_menu_item: &CategoryMenuItem,
     source_path: &str,
@@ -108,6 +233,7 @@ pub fn merge_category_to_project_config(
                 id: category_menu_item._id.to_string(),
                 name: category_menu_item.name.clone(),
                 interfaces: vec![],
+                lock: None,
             })
         }
     }
@@ -127,17 +253,23 @@ pub fn merge_interface_to_project_config(
     'project: for project in &mut yapi_config.project_list {
         for category in &mut project.categories {
             if category.id == cat_id {
-                if !category
+                match category
                     .interfaces
-                    .iter()
-                    .any(|interface| interface.id == interface_data_item._id.to_string())
+                    .iter_mut()
+                    .find(|interface| interface.id == interface_data_item._id.to_string())
                 {
-                    category.interfaces.push(YapiInterface {
+                    // 已存在的接口只更新 yapi 中的更新时间
+                    Some(interface) => interface.up_time = interface_data_item.up_time,
+                    None => category.interfaces.push(YapiInterface {
                         id: interface_data_item._id.to_string(),
                         name: interface_data_item.title.clone(),
                         path: Some(interface_data_item.path.clone()),
                         lock: Some(false),
-                    })
+                        up_time: interface_data_item.up_time,
+                        generated_up_time: None,
+                        generated_path: None,
+                        orphaned: None,
+                    }),
                 }
                 break 'project;
             }
@@ -149,6 +281,511 @@ pub fn merge_interface_to_project_config(
     Ok(())
 }
 
+// 用 list_menu 的结果整体更新项目的分类与接口，只写一次配置文件
+// 已有接口保留 lock 等本地字段，分类间移动的接口不视为删除
+// yapi 中已删除的接口标记为孤立，由使用者决定删除文件或标记为废弃
+pub fn merge_project_menu_to_project_config(
+    source_path: &str,
+    project_id: &str,
+    menu: &[MenuCategory],
+) -> Result<ProjectDiscoveryReport, io::Error> {
+    let mut yapi_config = get_project_config(source_path)?;
+    let project = yapi_config
+        .project_list
+        .iter_mut()
+        .find(|project| project.project_id == project_id)
+        .ok_or(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("项目 {} 不存在", project_id),
+        ))?;
+
+    let mut report = ProjectDiscoveryReport {
+        project_id: project_id.to_string(),
+        ..Default::default()
+    };
+    let local_interfaces: Vec<(String, YapiInterface)> = project
+        .categories
+        .iter()
+        .flat_map(|category| {
+            category
+                .interfaces
+                .iter()
+                .map(|interface| (category.id.clone(), interface.clone()))
+        })
+        .collect();
+    let mut categories = vec![];
+
+    for menu_category in menu {
+        let cat_id = menu_category._id.to_string();
+        let mut interfaces = vec![];
+
+        for item in &menu_category.list {
+            let id = item._id.to_string();
+            let local = local_interfaces
+                .iter()
+                .find(|(_, interface)| interface.id == id);
+
+            match local {
+                Some((old_cat_id, local)) => {
+                    if *old_cat_id != cat_id {
+                        report.moved.push(InterfaceMove {
+                            id: id.clone(),
+                            name: item.title.clone(),
+                            old_cat_id: old_cat_id.clone(),
+                            new_cat_id: cat_id.clone(),
+                            old_path: local.path.clone(),
+                            new_path: Some(item.path.clone()),
+                        });
+                    }
+                    report
+                        .renamed
+                        .extend(get_interface_rename(local, item, &cat_id));
+                    interfaces.push(YapiInterface {
+                        name: item.title.clone(),
+                        path: Some(item.path.clone()),
+                        up_time: item.up_time,
+                        orphaned: None,
+                        ..local.clone()
+                    });
+                }
+                None => {
+                    report.added.push(InterfaceChange {
+                        id,
+                        cat_id: cat_id.clone(),
+                        name: item.title.clone(),
+                        path: Some(item.path.clone()),
+                    });
+                    interfaces.push(get_new_yapi_interface(item));
+                }
+            }
+        }
+
+        // 分类的锁定状态以本地为准
+        let lock = project
+            .categories
+            .iter()
+            .find(|category| category.id == cat_id)
+            .and_then(|category| category.lock);
+        categories.push(YapiCategory {
+            id: cat_id,
+            name: menu_category.name.clone(),
+            interfaces,
+            lock,
+        });
+    }
+
+    for (cat_id, interface) in &local_interfaces {
+        let is_remote_exist = menu
+            .iter()
+            .flat_map(|menu_category| &menu_category.list)
+            .any(|item| item._id.to_string() == interface.id);
+        if is_remote_exist {
+            continue;
+        }
+
+        // 之前已标记为孤立的接口不再重复报告
+        if interface.orphaned != Some(true) {
+            report.removed.push(InterfaceChange {
+                id: interface.id.clone(),
+                cat_id: cat_id.clone(),
+                name: interface.name.clone(),
+                path: interface.path.clone(),
+            });
+        }
+
+        let orphaned_interface = YapiInterface {
+            orphaned: Some(true),
+            ..interface.clone()
+        };
+        match categories.iter_mut().find(|category| category.id == *cat_id) {
+            Some(category) => category.interfaces.push(orphaned_interface),
+            // 分类也已删除时保留原分类
+            None => {
+                let local_category = project
+                    .categories
+                    .iter()
+                    .find(|category| category.id == *cat_id);
+                categories.push(YapiCategory {
+                    id: cat_id.clone(),
+                    name: local_category.map_or(String::new(), |category| category.name.clone()),
+                    interfaces: vec![orphaned_interface],
+                    lock: local_category.and_then(|category| category.lock),
+                });
+            }
+        }
+    }
+
+    project.categories = categories;
+    write_project_config(source_path, yapi_config)?;
+
+    // yapi 中已删除的接口不再保留缓存
+    for interface in &report.removed {
+        remove_cache_entry(source_path, project_id, &interface.id)?;
+    }
+
+    Ok(report)
+}
+
+// 只把新增的接口记录到 yapi.json，不改动其他接口，本地没有的分类按 list_menu 的结果创建
+pub fn merge_added_interfaces_to_project_config(
+    source_path: &str,
+    project_id: &str,
+    menu: &[MenuCategory],
+    added: &[InterfaceChange],
+) -> Result<(), io::Error> {
+    let mut yapi_config = get_project_config(source_path)?;
+    let project = yapi_config
+        .project_list
+        .iter_mut()
+        .find(|project| project.project_id == project_id)
+        .ok_or(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("项目 {} 不存在", project_id),
+        ))?;
+
+    for menu_category in menu {
+        let cat_id = menu_category._id.to_string();
+        let items: Vec<_> = menu_category
+            .list
+            .iter()
+            .filter(|item| {
+                added
+                    .iter()
+                    .any(|interface| interface.id == item._id.to_string())
+            })
+            .collect();
+        if items.is_empty() {
+            continue;
+        }
+
+        let category = match project
+            .categories
+            .iter()
+            .position(|category| category.id == cat_id)
+        {
+            Some(index) => &mut project.categories[index],
+            None => {
+                project.categories.push(YapiCategory {
+                    id: cat_id,
+                    name: menu_category.name.clone(),
+                    interfaces: vec![],
+                    lock: None,
+                });
+                project.categories.last_mut().unwrap()
+            }
+        };
+        for item in items {
+            if !category
+                .interfaces
+                .iter()
+                .any(|interface| interface.id == item._id.to_string())
+            {
+                category.interfaces.push(get_new_yapi_interface(item));
+            }
+        }
+    }
+
+    write_project_config(source_path, yapi_config)?;
+
+    Ok(())
+}
+
+// 把来源文件解析出的项目整体合并到 yapi.json，只写一次配置文件
+// 已存在的接口只更新 yapi 中的更新时间
+pub fn merge_source_menu_to_project_config(
+    source_path: &str,
+    yapi_project_base_info: &YapiProjectBaseInfo,
+    source: ProjectSource,
+    menu: &[MenuCategory],
+) -> Result<(), io::Error> {
+    let mut yapi_config = get_project_config(source_path)?;
+    merge_yapi_project(
+        &mut yapi_config,
+        yapi_project_base_info,
+        "",
+        Some(source),
+        None,
+    )?;
+    let project_id = yapi_project_base_info._id.to_string();
+    let project = yapi_config
+        .project_list
+        .iter_mut()
+        .find(|project| project.project_id == project_id)
+        .ok_or(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("项目 {} 不存在", project_id),
+        ))?;
+
+    for menu_category in menu {
+        let cat_id = menu_category._id.to_string();
+        let category = match project
+            .categories
+            .iter()
+            .position(|category| category.id == cat_id)
+        {
+            Some(index) => &mut project.categories[index],
+            None => {
+                project.categories.push(YapiCategory {
+                    id: cat_id,
+                    name: menu_category.name.clone(),
+                    interfaces: vec![],
+                    lock: None,
+                });
+                project.categories.last_mut().unwrap()
+            }
+        };
+        for item in &menu_category.list {
+            match category
+                .interfaces
+                .iter_mut()
+                .find(|interface| interface.id == item._id.to_string())
+            {
+                Some(interface) => interface.up_time = item.up_time,
+                None => category.interfaces.push(get_new_yapi_interface(item)),
+            }
+        }
+    }
+
+    write_project_config(source_path, yapi_config)?;
+
+    Ok(())
+}
+
+fn get_new_yapi_interface(item: &InterfaceDataItem) -> YapiInterface {
+    YapiInterface {
+        id: item._id.to_string(),
+        name: item.title.clone(),
+        path: Some(item.path.clone()),
+        lock: Some(false),
+        up_time: item.up_time,
+        generated_up_time: None,
+        generated_path: None,
+        orphaned: None,
+    }
+}
+
+// 本地记录的接口在 yapi 中名称或路径发生了变化
+fn get_interface_rename(
+    local: &YapiInterface,
+    item: &InterfaceDataItem,
+    cat_id: &str,
+) -> Option<InterfaceRename> {
+    if local.name == item.title && local.path.as_deref() == Some(&item.path) {
+        return None;
+    }
+
+    Some(InterfaceRename {
+        id: local.id.clone(),
+        cat_id: cat_id.to_string(),
+        old_name: local.name.clone(),
+        new_name: item.title.clone(),
+        old_path: local.path.clone(),
+        new_path: Some(item.path.clone()),
+    })
+}
+
+// 对比 list_menu 的结果与本地记录的接口，找出新增、有更新与从未生成的接口
+pub fn get_remote_interface_report(
+    yapi_config: &YapiConfig,
+    project_id: &str,
+    menu: &[MenuCategory],
+) -> Result<RemoteInterfaceReport, io::Error> {
+    let project = yapi_config
+        .project_list
+        .iter()
+        .find(|project| project.project_id == project_id)
+        .ok_or(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("项目 {} 不存在", project_id),
+        ))?;
+
+    let mut report = RemoteInterfaceReport {
+        project_id: project_id.to_string(),
+        ..Default::default()
+    };
+
+    for menu_category in menu {
+        let cat_id = menu_category._id.to_string();
+
+        for item in &menu_category.list {
+            let id = item._id.to_string();
+            let change = InterfaceChange {
+                id: id.clone(),
+                cat_id: cat_id.clone(),
+                name: item.title.clone(),
+                path: Some(item.path.clone()),
+            };
+            let local = project
+                .categories
+                .iter()
+                .flat_map(|category| &category.interfaces)
+                .find(|interface| interface.id == id);
+
+            let local = match local {
+                Some(local) => local,
+                None => {
+                    report.added.push(change);
+                    continue;
+                }
+            };
+
+            report
+                .renamed
+                .extend(get_interface_rename(local, item, &cat_id));
+
+            match (item.up_time, local.generated_up_time) {
+                (_, None) => report.ungenerated.push(change),
+                (Some(up_time), Some(generated_up_time)) if up_time > generated_up_time => {
+                    report.changed.push(change)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+// 接口被锁定的原因，接口本身、所属分类或所属项目任一锁定即视为锁定
+// 没有接口 id 时按接口路径查找
+pub fn get_interface_lock_reason(
+    yapi_config: &YapiConfig,
+    interface_id: Option<&str>,
+    path: Option<&str>,
+) -> Option<String> {
+    for project in &yapi_config.project_list {
+        for category in &project.categories {
+            let interface = category
+                .interfaces
+                .iter()
+                .find(|interface| match interface_id {
+                    Some(interface_id) => interface.id == interface_id,
+                    None => path.is_some() && interface.path.as_deref() == path,
+                });
+
+            if let Some(interface) = interface {
+                let project_name = project
+                    .project_name
+                    .clone()
+                    .unwrap_or(project.project_id.clone());
+
+                return if interface.lock == Some(true) {
+                    Some(format!("接口 {} 已锁定", interface.name))
+                } else if category.lock == Some(true) {
+                    Some(format!("分类 {} 已锁定", category.name))
+                } else if project.lock == Some(true) {
+                    Some(format!("项目 {} 已锁定", project_name))
+                } else {
+                    None
+                };
+            }
+        }
+    }
+
+    None
+}
+
+// 写入前检查锁定状态，锁定的接口需要强制写入
+pub fn check_interface_lock(
+    yapi_config: &YapiConfig,
+    interface_id: Option<&str>,
+    path: Option<&str>,
+) -> Result<(), io::Error> {
+    match get_interface_lock_reason(yapi_config, interface_id, path) {
+        Some(reason) => Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("{}，如需覆盖请强制写入", reason),
+        )),
+        None => Ok(()),
+    }
+}
+
+// 设置项目、分类或接口的锁定状态，cat_id 与 interface_id 都为空时设置项目
+pub fn update_lock(
+    source_path: &str,
+    project_id: &str,
+    cat_id: Option<&str>,
+    interface_id: Option<&str>,
+    lock: bool,
+) -> Result<(), io::Error> {
+    let mut yapi_config = get_project_config(source_path)?;
+    let project = yapi_config
+        .project_list
+        .iter_mut()
+        .find(|project| project.project_id == project_id)
+        .ok_or(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("项目 {} 不存在", project_id),
+        ))?;
+
+    let target = match (cat_id, interface_id) {
+        (_, Some(interface_id)) => project
+            .categories
+            .iter_mut()
+            .flat_map(|category| category.interfaces.iter_mut())
+            .find(|interface| interface.id == interface_id)
+            .map(|interface| &mut interface.lock),
+        (Some(cat_id), None) => project
+            .categories
+            .iter_mut()
+            .find(|category| category.id == cat_id)
+            .map(|category| &mut category.lock),
+        (None, None) => Some(&mut project.lock),
+    };
+
+    match target {
+        Some(target) => *target = Some(lock),
+        None => {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "项目 {} 中不存在 {}",
+                    project_id,
+                    interface_id.or(cat_id).unwrap_or_default()
+                ),
+            ))
+        }
+    }
+
+    write_project_config(source_path, yapi_config)?;
+    Ok(())
+}
+
+// 写入文件后记录生成时接口的更新时间与路径
+// 增量同步时据此跳过未变更的接口，路径用于发现 yapi 中改名的接口
+// yapi_config 为写入文件前读取的配置，避免每个文件重复读取
+pub fn update_generated_info(
+    source_path: &str,
+    mut yapi_config: YapiConfig,
+    interface_id: &str,
+    up_time: Option<u64>,
+    path: &str,
+) -> Result<(), io::Error> {
+    let interface = yapi_config
+        .project_list
+        .iter_mut()
+        .flat_map(|project| project.categories.iter_mut())
+        .flat_map(|category| category.interfaces.iter_mut())
+        .find(|interface| interface.id == interface_id);
+
+    if let Some(interface) = interface {
+        interface.generated_up_time = up_time.or(interface.up_time);
+        interface.generated_path = Some(path.to_string());
+        write_project_config(source_path, yapi_config)?;
+    }
+
+    Ok(())
+}
+
+// 接口需要重新生成：未生成过，或 yapi 中的更新时间晚于上次生成的时间
+// 没有更新时间的接口（如文件导入）无法判断，总是重新生成
+pub fn is_interface_stale(interface: &YapiInterface) -> bool {
+    match (interface.up_time, interface.generated_up_time) {
+        (Some(up_time), Some(generated_up_time)) => up_time > generated_up_time,
+        _ => true,
+    }
+}
+
 pub fn merge_config_projects(source_path: &str, other_path: &str) -> Result<(), io::Error> {
     let mut source_config = get_project_config(source_path)?;
     let other_config = get_specific_project_config(other_path)?;
@@ -0,0 +1,445 @@
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+use crate::{
+    models::yapi::{
+        config::YapiConfig,
+        diff::{ChangeReport, FieldChange, FieldChangeKind, InterfaceDiff},
+        interface::{InterfaceData, JsonValue, ObjectType, Root, WebType},
+    },
+    services::conversion::string_to_path_buf,
+};
+
+use super::{
+    cache::read_cache_entry, config::get_project_config, interface::get_interface_roots,
+    resolver::common::get_ts_interface_name,
+};
+
+// 报告写入代码库根目录，便于评审与 CI 查看
+pub const CHANGE_REPORT_FILE_NAME: &str = "yapi-changes";
+
+// 字段在生成模型中的形态
+#[derive(PartialEq)]
+struct FieldShape {
+    type_name: String,
+    required: bool,
+    // 嵌套对象生成的 interface 名称
+    interface_name: Option<String>,
+}
+
+// 比较缓存中上次生成时与最新获取的接口详情，只包含有变化的接口
+pub fn get_change_report(
+    source_path: &str,
+    project_id: Option<&str>,
+) -> Result<ChangeReport, io::Error> {
+    let project_config = get_project_config(source_path)?;
+    let mut report = ChangeReport::default();
+
+    for project in &project_config.project_list {
+        if project_id.map_or(false, |project_id| project_id != project.project_id) {
+            continue;
+        }
+
+        for interface in project
+            .categories
+            .iter()
+            .flat_map(|category| &category.interfaces)
+        {
+            // 无法解析的缓存同样视为缺失
+            let cached_interface =
+                match read_cache_entry(source_path, &project.project_id, &interface.id) {
+                    Ok(Some(cached_interface)) => cached_interface,
+                    _ => continue,
+                };
+
+            let interface_diff = match (&cached_interface.generated, interface.generated_up_time) {
+                (Some(generated), _) => {
+                    get_interface_diff(&project.project_id, Some(generated), &cached_interface.data)
+                }
+                // 从未生成过的接口
+                (None, None) => {
+                    get_interface_diff(&project.project_id, None, &cached_interface.data)
+                }
+                // 生成过但没有记录当时的详情，无法比较
+                (None, Some(_)) => continue,
+            };
+
+            if interface_diff.is_new || !interface_diff.changes.is_empty() {
+                report.interfaces.push(interface_diff);
+            }
+        }
+    }
+
+    report.breaking = report
+        .interfaces
+        .iter()
+        .filter(|interface| interface.breaking)
+        .count();
+
+    Ok(report)
+}
+
+// 写入文件前比较上次生成时与本次写入的接口详情，没有变化时返回 None
+// 生成过但没有记录当时详情的接口无法比较，同样返回 None
+pub fn get_write_diff(
+    source_path: &str,
+    yapi_config: &YapiConfig,
+    data: &InterfaceData,
+) -> Option<InterfaceDiff> {
+    let project_id = data.project_id.to_string();
+    let interface_id = data._id.to_string();
+    let generated = read_cache_entry(source_path, &project_id, &interface_id)
+        .ok()
+        .flatten()
+        .and_then(|cached_interface| cached_interface.generated);
+
+    if generated.is_none() {
+        let is_generated = yapi_config
+            .project_list
+            .iter()
+            .flat_map(|project| &project.categories)
+            .flat_map(|category| &category.interfaces)
+            .any(|interface| {
+                interface.id == interface_id
+                    && (interface.generated_up_time.is_some() || interface.generated_path.is_some())
+            });
+        if is_generated {
+            return None;
+        }
+    }
+
+    let interface_diff = get_interface_diff(&project_id, generated.as_ref(), data);
+    if interface_diff.is_new || !interface_diff.changes.is_empty() {
+        Some(interface_diff)
+    } else {
+        None
+    }
+}
+
+// 比较上次生成时与当前的接口详情，previous 为空表示新接口
+pub fn get_interface_diff(
+    project_id: &str,
+    previous: Option<&InterfaceData>,
+    current: &InterfaceData,
+) -> InterfaceDiff {
+    let mut changes = vec![];
+
+    if let Some(previous) = previous {
+        let (previous_req, previous_resp, _) = get_interface_roots(previous);
+        let (current_req, current_resp, _) = get_interface_roots(current);
+
+        diff_roots(WebType::Request, &previous_req, &current_req, &mut changes);
+        diff_roots(
+            WebType::Response,
+            &previous_resp,
+            &current_resp,
+            &mut changes,
+        );
+    }
+
+    InterfaceDiff {
+        project_id: project_id.to_string(),
+        interface_id: current._id.to_string(),
+        title: current.title.clone(),
+        method: current.method.clone(),
+        path: current.path.clone(),
+        is_new: previous.is_none(),
+        breaking: changes.iter().any(|change| change.breaking),
+        changes,
+    }
+}
+
+fn diff_roots(web_type: WebType, previous: &Root, current: &Root, changes: &mut Vec<FieldChange>) {
+    let web_type_name = match web_type {
+        WebType::Request => "request",
+        WebType::Response => "response",
+    };
+    let previous_name = get_ts_interface_name(&previous.interface_name, &previous.key);
+    let current_name = get_ts_interface_name(&current.interface_name, &current.key);
+
+    if previous_name != current_name {
+        changes.push(FieldChange {
+            web_type: web_type_name.to_string(),
+            field: String::new(),
+            kind: FieldChangeKind::InterfaceRenamed,
+            before: Some(previous_name),
+            after: Some(current_name),
+            breaking: true,
+        });
+    }
+
+    let previous_fields = get_field_shapes(previous);
+    let current_fields = get_field_shapes(current);
+    let mut fields: Vec<_> = previous_fields
+        .keys()
+        .chain(current_fields.keys())
+        .collect();
+    fields.sort();
+    fields.dedup();
+
+    for field in fields {
+        let mut push = |kind: FieldChangeKind, before: Option<String>, after: Option<String>| {
+            let breaking = is_breaking(&web_type, &kind, current_fields.get(field));
+            changes.push(FieldChange {
+                web_type: web_type_name.to_string(),
+                field: field.clone(),
+                kind,
+                before,
+                after,
+                breaking,
+            });
+        };
+
+        match (previous_fields.get(field), current_fields.get(field)) {
+            (None, Some(current)) => push(
+                FieldChangeKind::Added,
+                None,
+                Some(current.type_name.clone()),
+            ),
+            (Some(previous), None) => push(
+                FieldChangeKind::Removed,
+                Some(previous.type_name.clone()),
+                None,
+            ),
+            (Some(previous), Some(current)) => {
+                if previous.type_name != current.type_name {
+                    push(
+                        FieldChangeKind::TypeChanged,
+                        Some(previous.type_name.clone()),
+                        Some(current.type_name.clone()),
+                    );
+                }
+                match (previous.required, current.required) {
+                    (true, false) => push(FieldChangeKind::RequiredToOptional, None, None),
+                    (false, true) => push(FieldChangeKind::OptionalToRequired, None, None),
+                    _ => {}
+                }
+                if let (Some(previous_name), Some(current_name)) =
+                    (&previous.interface_name, &current.interface_name)
+                {
+                    if previous_name != current_name {
+                        push(
+                            FieldChangeKind::InterfaceRenamed,
+                            Some(previous_name.clone()),
+                            Some(current_name.clone()),
+                        );
+                    }
+                }
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+// 请求：新增必填、删除字段、变为必填会让调用方编译失败
+// 响应：删除字段、变为可选会让读取方出错
+// 类型变化与嵌套 interface 改名对两者都是破坏性的
+fn is_breaking(web_type: &WebType, kind: &FieldChangeKind, current: Option<&FieldShape>) -> bool {
+    match (web_type, kind) {
+        (_, FieldChangeKind::TypeChanged)
+        | (_, FieldChangeKind::InterfaceRenamed)
+        | (_, FieldChangeKind::Removed) => true,
+        (WebType::Request, FieldChangeKind::Added) => current.map_or(false, |field| field.required),
+        (WebType::Request, FieldChangeKind::OptionalToRequired) => true,
+        (WebType::Response, FieldChangeKind::RequiredToOptional) => true,
+        _ => false,
+    }
+}
+
+fn get_field_shapes(root: &Root) -> BTreeMap<String, FieldShape> {
+    let mut shapes = BTreeMap::new();
+    collect_field_shapes(
+        root.children.as_deref().unwrap_or_default(),
+        "",
+        &mut shapes,
+    );
+    shapes
+}
+
+fn collect_field_shapes(
+    values: &[JsonValue],
+    prefix: &str,
+    shapes: &mut BTreeMap<String, FieldShape>,
+) {
+    for value in values {
+        match value {
+            JsonValue::Atom(atom) => {
+                let type_name = match &atom.format {
+                    Some(format) => format!("{}({})", atom.value, format),
+                    None => atom.value.clone(),
+                };
+                shapes.insert(
+                    format!("{}{}", prefix, atom.key),
+                    FieldShape {
+                        type_name,
+                        required: atom.required,
+                        interface_name: None,
+                    },
+                );
+            }
+            JsonValue::ObjectLike(object_like) => {
+                let field = format!("{}{}", prefix, object_like.key);
+                let has_nodes = !object_like.nodes.is_empty();
+                let type_name = match (&object_like.object_type, has_nodes) {
+                    (ObjectType::Object, _) => "object",
+                    (ObjectType::Array, true) => "object[]",
+                    (ObjectType::Array, false) => "array",
+                };
+                let interface_name = if has_nodes {
+                    Some(get_ts_interface_name(
+                        &object_like.interface_name,
+                        &object_like.key,
+                    ))
+                } else {
+                    None
+                };
+                let sub_prefix = match object_like.object_type {
+                    ObjectType::Object => format!("{}.", field),
+                    ObjectType::Array => format!("{}[].", field),
+                };
+                let sub_values: Vec<_> = object_like
+                    .nodes
+                    .iter()
+                    .map(|node| node.value.clone())
+                    .collect();
+
+                shapes.insert(
+                    field,
+                    FieldShape {
+                        type_name: type_name.to_string(),
+                        required: object_like.required,
+                        interface_name,
+                    },
+                );
+                collect_field_shapes(&sub_values, &sub_prefix, shapes);
+            }
+            JsonValue::Null => {}
+        }
+    }
+}
+
+fn get_change_report_paths(source_path: &str) -> (PathBuf, PathBuf) {
+    let base_path: PathBuf = string_to_path_buf(source_path.to_string());
+
+    (
+        base_path.join(format!("{}.md", CHANGE_REPORT_FILE_NAME)),
+        base_path.join(format!("{}.json", CHANGE_REPORT_FILE_NAME)),
+    )
+}
+
+// 报告同时保存为 markdown 与 json，已有报告时只有 overwrite 为 true 才覆盖
+pub fn write_change_report(
+    source_path: &str,
+    mut report: ChangeReport,
+    overwrite: bool,
+) -> Result<ChangeReport, io::Error> {
+    let (markdown_path, json_path) = get_change_report_paths(source_path);
+
+    if !overwrite {
+        if let Some(file_path) = [&markdown_path, &json_path]
+            .into_iter()
+            .find(|file_path| file_path.exists())
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!(
+                    "变更报告 {} 已存在，如需覆盖请强制写入",
+                    file_path.to_string_lossy()
+                ),
+            ));
+        }
+    }
+
+    report.markdown_path = Some(markdown_path.to_string_lossy().to_string());
+    report.json_path = Some(json_path.to_string_lossy().to_string());
+
+    fs::write(&markdown_path, get_change_report_markdown(&report))?;
+    fs::write(&json_path, serde_json::to_string_pretty(&report)?)?;
+
+    Ok(report)
+}
+
+// 把单个接口的变化并入已有报告，替换该接口之前的记录，无法解析的报告视为不存在
+pub fn merge_interface_diff_to_change_report(
+    source_path: &str,
+    interface_diff: InterfaceDiff,
+) -> Result<ChangeReport, io::Error> {
+    let (_, json_path) = get_change_report_paths(source_path);
+    let mut report: ChangeReport = fs::read_to_string(json_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    report.interfaces.retain(|interface| {
+        interface.project_id != interface_diff.project_id
+            || interface.interface_id != interface_diff.interface_id
+    });
+    report.interfaces.push(interface_diff);
+    report.breaking = report
+        .interfaces
+        .iter()
+        .filter(|interface| interface.breaking)
+        .count();
+
+    write_change_report(source_path, report, true)
+}
+
+pub fn get_change_report_markdown(report: &ChangeReport) -> String {
+    let mut res_string = format!(
+        "# 接口变更报告\n\n共 {} 个接口有变化，其中 {} 个包含破坏性变更\n",
+        report.interfaces.len(),
+        report.breaking
+    );
+
+    for interface in &report.interfaces {
+        let tag = if interface.is_new {
+            "[新增] "
+        } else if interface.breaking {
+            "[破坏性] "
+        } else {
+            ""
+        };
+        res_string += &format!(
+            "\n## {}{} {} {}\n\n接口 id：{}\n",
+            tag, interface.method, interface.path, interface.title, interface.interface_id
+        );
+
+        if interface.changes.is_empty() {
+            continue;
+        }
+
+        res_string += "\n| 位置 | 字段 | 变更 | 变更前 | 变更后 | 破坏性 |\n";
+        res_string += "| --- | --- | --- | --- | --- | --- |\n";
+        for change in &interface.changes {
+            res_string += &format!(
+                "| {} | {} | {} | {} | {} | {} |\n",
+                if change.web_type == "request" {
+                    "请求"
+                } else {
+                    "响应"
+                },
+                if change.field.is_empty() {
+                    "(根)"
+                } else {
+                    &change.field
+                },
+                get_change_kind_name(&change.kind),
+                change.before.as_deref().unwrap_or("-"),
+                change.after.as_deref().unwrap_or("-"),
+                if change.breaking { "是" } else { "否" }
+            );
+        }
+    }
+
+    res_string
+}
+
+fn get_change_kind_name(kind: &FieldChangeKind) -> &'static str {
+    match kind {
+        FieldChangeKind::Added => "新增",
+        FieldChangeKind::Removed => "删除",
+        FieldChangeKind::RequiredToOptional => "必填变为可选",
+        FieldChangeKind::OptionalToRequired => "可选变为必填",
+        FieldChangeKind::TypeChanged => "类型变化",
+        FieldChangeKind::InterfaceRenamed => "interface 改名",
+    }
+}
@@ -0,0 +1,148 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    models::yapi::{
+        history::{HistoryEntry, HistoryVersion},
+        interface::InterfaceData,
+    },
+    services::conversion::{string_to_content_hash, string_to_path_buf},
+};
+
+use super::interface::get_interface_roots;
+
+// 历史版本位于代码库根目录下，按 项目/接口 id/版本 存放
+pub const HISTORY_DIR_NAME: &str = ".yapi-history";
+
+fn get_history_dir(source_path: &str, project_id: &str, interface_id: &str) -> PathBuf {
+    string_to_path_buf(source_path.to_string())
+        .join(HISTORY_DIR_NAME)
+        .join(project_id)
+        .join(interface_id)
+}
+
+// 按生成所用的模型计算哈希，只改动了 up_time 等不影响生成结果的字段时不产生新版本
+fn get_history_hash(data: &InterfaceData) -> Result<String, io::Error> {
+    let (req_root, resp_root, req_form_type) = get_interface_roots(data);
+
+    Ok(string_to_content_hash(&serde_json::to_string(&(
+        &data.method,
+        &req_root,
+        &resp_root,
+        &req_form_type,
+    ))?))
+}
+
+// 记录一次同步的接口详情，与任一已有版本相同时不写入，返回是否新增了版本
+pub fn record_history(source_path: &str, data: &InterfaceData) -> Result<bool, io::Error> {
+    let project_id = data.project_id.to_string();
+    let interface_id = data._id.to_string();
+    let hash = get_history_hash(data)?;
+    let dir_path = get_history_dir(source_path, &project_id, &interface_id);
+
+    // 文件名为 同步时间戳-内容哈希，不需要读取已有版本的内容
+    if get_history_versions(&dir_path)?
+        .iter()
+        .any(|version| version.ends_with(&format!("-{}", hash)))
+    {
+        return Ok(false);
+    }
+
+    let synced_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let history_version = HistoryVersion {
+        version: format!("{}-{}", synced_at, hash),
+        hash,
+        synced_at,
+        up_time: data.up_time,
+        data: data.clone(),
+    };
+
+    fs::create_dir_all(&dir_path)?;
+    // 历史版本只用于本地比较与回滚，不需要提交到代码库
+    let gitignore_path = string_to_path_buf(source_path.to_string())
+        .join(HISTORY_DIR_NAME)
+        .join(".gitignore");
+    if !gitignore_path.exists() {
+        fs::write(gitignore_path, "*\n")?;
+    }
+    fs::write(
+        dir_path.join(format!("{}.json", history_version.version)),
+        serde_json::to_string(&history_version)?,
+    )?;
+
+    Ok(true)
+}
+
+// 目录下全部版本文件的版本号
+fn get_history_versions(dir_path: &Path) -> Result<Vec<String>, io::Error> {
+    let mut versions = vec![];
+    if !dir_path.exists() {
+        return Ok(versions);
+    }
+
+    for entry in fs::read_dir(dir_path)? {
+        let path = entry?.path();
+        if path
+            .extension()
+            .map_or(true, |extension| extension != "json")
+        {
+            continue;
+        }
+        if let Some(version) = path.file_stem() {
+            versions.push(version.to_string_lossy().to_string());
+        }
+    }
+
+    Ok(versions)
+}
+
+// 接口的全部版本，按同步时间从早到晚排列
+pub fn list_history(
+    source_path: &str,
+    project_id: &str,
+    interface_id: &str,
+) -> Result<Vec<HistoryEntry>, io::Error> {
+    let dir_path = get_history_dir(source_path, project_id, interface_id);
+    let mut versions = vec![];
+
+    for version in get_history_versions(&dir_path)? {
+        let history_version: HistoryVersion = serde_json::from_str(&fs::read_to_string(
+            dir_path.join(format!("{}.json", version)),
+        )?)?;
+        versions.push(HistoryEntry::from(&history_version));
+    }
+
+    versions.sort_by(|a, b| {
+        a.synced_at
+            .cmp(&b.synced_at)
+            .then(a.version.cmp(&b.version))
+    });
+    Ok(versions)
+}
+
+pub fn get_history_version(
+    source_path: &str,
+    project_id: &str,
+    interface_id: &str,
+    version: &str,
+) -> Result<HistoryVersion, io::Error> {
+    // 版本号会作为文件名使用
+    let is_legal_version = version
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-');
+    let file_path =
+        get_history_dir(source_path, project_id, interface_id).join(format!("{}.json", version));
+    if !is_legal_version || !file_path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("接口 {} 不存在版本 {}", interface_id, version),
+        ));
+    }
+
+    Ok(serde_json::from_str(&fs::read_to_string(file_path)?)?)
+}
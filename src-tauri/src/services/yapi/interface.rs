@@ -1,83 +1,267 @@
-use std::{fs, io, path::PathBuf};
+use std::{collections::HashMap, fs, io, path::PathBuf};
 
 use tauri::AppHandle;
 
 use crate::{
     models::yapi::{
-        interface::{FormType, InterfaceData, InterfaceFetchParams, WebType},
-        web_response::YapiResponse,
+        config::{KeyCase, ManualEditPolicy, YapiConfig, ZodMode},
+        interface::{FormType, InterfaceData, InterfaceFetchParams, Root, WebType},
+        queue::ResolvedInterface,
+        web_response::Attempted,
     },
-    services::reqwest::{get_data, get_reqwest_client},
+    services::reqwest::{get_reqwest_client, get_retry_policy},
 };
 
 use super::{
-    config::get_project_config,
+    client::get_yapi_data,
+    config::{get_project_config, get_yapi_server},
+    provenance::{check_manual_edit, stamp_provenance},
     resolver::{
         common::{get_json, get_path_arr, get_req_body_type, get_request_json},
-        form_resolver, json_resolver,
+        dart_resolver, form_resolver, go_resolver, json_resolver,
+        key_case::{check_duplicate_keys, convert_key, get_wire_mapper_ts},
+        zod_resolver::{self, ZOD_IMPORT},
     },
 };
 
 const ADD_INTERFACE_TASK_API: &str = "api/interface/get";
 
-// 获取接口详情
+// 获取接口详情，同时返回请求的尝试次数
 pub async fn fetch_interface_detail(
     fetch_interface_params: InterfaceFetchParams,
     app_handle: &AppHandle,
-) -> Result<InterfaceData, io::Error> {
-    let client = get_reqwest_client(&app_handle)?;
-    let mut project_config = get_project_config(&fetch_interface_params.source_path)?;
-    if !project_config.base_url.ends_with('/') {
-        project_config.base_url.push('/');
-    }
+) -> Result<Attempted<InterfaceData>, io::Error> {
+    let server = get_yapi_server(
+        &fetch_interface_params.source_path,
+        &fetch_interface_params.token,
+        None,
+    )?;
+    let client = get_reqwest_client(app_handle, server.proxy.as_deref())?;
 
     let url = format!(
         "{}{}?token={}&id={}",
-        project_config.base_url,
+        server.base_url,
         ADD_INTERFACE_TASK_API,
         fetch_interface_params.token,
         fetch_interface_params.interface_id
     );
 
-    match get_data::<YapiResponse<InterfaceData>>(client, url).await {
-        Ok(res) => Ok(res.data),
-        Err(err) => Err(io::Error::new(
-            io::ErrorKind::Other,
-            format!("获取接口详情失败: {}", err.to_string()),
-        )),
-    }
+    let retry_policy = get_retry_policy(app_handle)?;
+
+    Ok(get_yapi_data::<InterfaceData>(client, url, "获取接口详情", &retry_policy).await?)
 }
 
 // 接口转ts字符串
-pub fn get_interface_ts_string(data: &InterfaceData) -> Result<String, String> {
+pub fn get_interface_ts_string(
+    data: &InterfaceData,
+    project_config: &YapiConfig,
+) -> Result<String, String> {
     if let Err(e) = is_legal(data) {
         return Err(e);
     }
 
-    let resp_ts_string = json_resolver::get_ts_string(
+    let key_case = project_config.key_case.clone().unwrap_or_default();
+    let (req_root, resp_root, req_form_type) = get_interface_roots(data);
+
+    match req_form_type {
+        FormType::Form => check_duplicate_keys(&req_root, &|key: &str| {
+            form_resolver::get_field_name(key, &key_case)
+        })?,
+        FormType::Json => {
+            check_duplicate_keys(&req_root, &|key: &str| convert_key(key, &key_case))?
+        }
+    }
+    check_duplicate_keys(&resp_root, &|key: &str| convert_key(key, &key_case))?;
+
+    let resp_ts_string = json_resolver::get_root_ts(&resp_root, &key_case);
+    let req_ts_string = match req_form_type {
+        FormType::Form => form_resolver::get_root_ts(&req_root, &key_case),
+        FormType::Json => json_resolver::get_root_ts(&req_root, &key_case),
+    };
+
+    let mut ts_string = format!("{}\n{}", req_ts_string, resp_ts_string);
+
+    // 属性名被重命名后，额外生成与接口字段互转的函数
+    if key_case != KeyCase::Original {
+        ts_string = format!(
+            "{}\n{}\n{}",
+            ts_string,
+            get_wire_mapper_ts(WebType::Request, &req_root, &key_case),
+            get_wire_mapper_ts(WebType::Response, &resp_root, &key_case)
+        );
+    }
+
+    // import 需要位于文件开头，同文件已有 interface，不再导出推导的类型
+    if project_config.zod_mode == Some(ZodMode::Inline) {
+        ts_string = format!(
+            "{}\n{}\n{}",
+            ZOD_IMPORT,
+            ts_string,
+            get_schema_string(&req_root, &resp_root, &req_form_type, &key_case, false)
+        );
+    }
+
+    Ok(ts_string)
+}
+
+// 生成接口对应的全部输出
+pub fn get_resolved_interface(
+    data: InterfaceData,
+    project_config: &YapiConfig,
+) -> Result<ResolvedInterface, String> {
+    // 每个生成的文件都带上来源信息
+    let stamp = |content: String| stamp_provenance(content, &data);
+    let ts_string = stamp(get_interface_ts_string(&data, project_config)?);
+    let schema_string = get_interface_schema_string(&data, project_config)?.map(stamp);
+    let dart_string = get_interface_dart_string(&data, project_config)?.map(stamp);
+    let go_string = get_interface_go_string(&data, project_config)?.map(stamp);
+
+    Ok(ResolvedInterface {
+        interface: data,
+        ts_string,
+        schema_string,
+        dart_string,
+        go_string,
+    })
+}
+
+// 接口转 dart 字符串，仅在配置了 dart_path 时返回
+pub fn get_interface_dart_string(
+    data: &InterfaceData,
+    project_config: &YapiConfig,
+) -> Result<Option<String>, String> {
+    if !is_dart_enabled(project_config) {
+        return Ok(None);
+    }
+    is_legal(data)?;
+
+    let (req_root, resp_root, _) = get_interface_roots(data);
+    let mut declared = HashMap::new();
+
+    Ok(Some(format!(
+        "{}\n{}",
+        dart_resolver::get_root_dart(&req_root, &mut declared),
+        dart_resolver::get_root_dart(&resp_root, &mut declared)
+    )))
+}
+
+fn is_dart_enabled(project_config: &YapiConfig) -> bool {
+    match &project_config.dart_path {
+        Some(dart_path) => !dart_path.is_empty(),
+        None => false,
+    }
+}
+
+// 接口转 go 字符串，仅在配置了 go_path 时返回，包名由所属分类生成
+pub fn get_interface_go_string(
+    data: &InterfaceData,
+    project_config: &YapiConfig,
+) -> Result<Option<String>, String> {
+    if !is_go_enabled(project_config) {
+        return Ok(None);
+    }
+    is_legal(data)?;
+
+    let (req_root, resp_root, _) = get_interface_roots(data);
+    let cat_id = data.catid.to_string();
+    let category_name = project_config
+        .project_list
+        .iter()
+        .flat_map(|project| project.categories.iter())
+        .find(|category| category.id == cat_id)
+        .map(|category| category.name.as_str());
+    let package_name = go_resolver::get_go_package_name(category_name, &data.path, data.catid);
+
+    Ok(Some(go_resolver::get_go_file_string(
+        &package_name,
+        &data.path,
+        &[&req_root, &resp_root],
+    )))
+}
+
+fn is_go_enabled(project_config: &YapiConfig) -> bool {
+    match &project_config.go_path {
+        Some(go_path) => !go_path.is_empty(),
+        None => false,
+    }
+}
+
+// 接口转 zod schema 字符串，仅在配置为单独文件输出时返回
+pub fn get_interface_schema_string(
+    data: &InterfaceData,
+    project_config: &YapiConfig,
+) -> Result<Option<String>, String> {
+    if project_config.zod_mode != Some(ZodMode::Sibling) {
+        return Ok(None);
+    }
+    is_legal(data)?;
+
+    let key_case = project_config.key_case.clone().unwrap_or_default();
+    let (req_root, resp_root, req_form_type) = get_interface_roots(data);
+
+    Ok(Some(format!(
+        "{}\n{}",
+        ZOD_IMPORT,
+        get_schema_string(&req_root, &resp_root, &req_form_type, &key_case, true)
+    )))
+}
+
+// 生成请求与响应的根节点模型
+pub fn get_interface_roots(data: &InterfaceData) -> (Root, Root, FormType) {
+    let resp_root = json_resolver::generate_root(
         WebType::Response,
         data,
         &get_json(data.res_body.clone().unwrap_or("".to_string())),
     );
-    let req_ts_string;
 
     let request_json = get_json(get_request_json(data));
 
-    if data.method == "POST" {
-        // post请求可能是json也可能是form
-        match get_req_body_type(data) {
-            FormType::Form => {
-                req_ts_string = form_resolver::get_ts_string(WebType::Request, data, &request_json)
-            }
-            FormType::Json => {
-                req_ts_string = json_resolver::get_ts_string(WebType::Request, data, &request_json)
-            }
-        };
+    // post请求可能是json也可能是form
+    let req_form_type = if data.method == "POST" {
+        get_req_body_type(data)
     } else {
-        req_ts_string = form_resolver::get_ts_string(WebType::Request, data, &request_json)
-    }
+        FormType::Form
+    };
 
-    Ok(format!("{}\n{}", req_ts_string, resp_ts_string))
+    let req_root = match req_form_type {
+        FormType::Form => form_resolver::generate_root(WebType::Request, data, &request_json),
+        FormType::Json => json_resolver::generate_root(WebType::Request, data, &request_json),
+    };
+
+    (req_root, resp_root, req_form_type)
+}
+
+// 不包含 zod 的 import，由调用方放在文件开头
+fn get_schema_string(
+    req_root: &Root,
+    resp_root: &Root,
+    req_form_type: &FormType,
+    key_case: &KeyCase,
+    infer_type: bool,
+) -> String {
+    let mut declared = HashMap::new();
+    let req_schema_string = match req_form_type {
+        FormType::Form => zod_resolver::get_root_schema_ts(
+            req_root,
+            &|key: &str| form_resolver::get_field_name(key, key_case),
+            &mut declared,
+            infer_type,
+        ),
+        FormType::Json => zod_resolver::get_root_schema_ts(
+            req_root,
+            &|key: &str| convert_key(key, key_case),
+            &mut declared,
+            infer_type,
+        ),
+    };
+    let resp_schema_string = zod_resolver::get_root_schema_ts(
+        resp_root,
+        &|key: &str| convert_key(key, key_case),
+        &mut declared,
+        infer_type,
+    );
+
+    format!("{}\n{}", req_schema_string, resp_schema_string)
 }
 
 fn is_legal(data: &InterfaceData) -> Result<(), String> {
@@ -87,11 +271,48 @@ fn is_legal(data: &InterfaceData) -> Result<(), String> {
     Ok(())
 }
 
-pub fn write_content_to_interface_path(
-    path: String,
-    source_path: &str,
-    content: String,
-) -> Result<(), io::Error> {
+// 写入一个接口生成的全部文件，先检查全部文件是否被手动修改，任一文件拒绝覆盖时不写入任何文件
+// 返回需要提示的信息
+pub fn write_generated_files(
+    files: Vec<(PathBuf, String)>,
+    policy: &ManualEditPolicy,
+    force: bool,
+) -> Result<Vec<String>, io::Error> {
+    let mut warnings = vec![];
+
+    for (file_path, _) in &files {
+        warnings.extend(check_manual_edit(file_path, policy, force)?);
+    }
+
+    for (file_path, content) in files {
+        if let Some(dir_path) = file_path.parent() {
+            fs::create_dir_all(dir_path)?;
+        }
+        fs::write(file_path, content)?;
+    }
+
+    Ok(warnings)
+}
+
+// 接口路径对应的 ts 文件
+pub fn get_ts_file_path(path: String, source_path: &str) -> Result<PathBuf, io::Error> {
+    let file_path = get_interface_file_path(path, source_path)?;
+
+    Ok(PathBuf::from(format!("{}.ts", file_path.to_string_lossy())))
+}
+
+// 与 interface 文件同目录的 .schema.ts 文件
+pub fn get_schema_file_path(path: String, source_path: &str) -> Result<PathBuf, io::Error> {
+    let file_path = get_interface_file_path(path, source_path)?;
+
+    Ok(PathBuf::from(format!(
+        "{}.schema.ts",
+        file_path.to_string_lossy()
+    )))
+}
+
+// 接口路径对应的 types 文件路径（不含扩展名）
+pub fn get_interface_file_path(path: String, source_path: &str) -> Result<PathBuf, io::Error> {
     let project_config = get_project_config(source_path)?;
     let path_arr = get_path_arr(path);
     let mut file_path = PathBuf::from(source_path).join(project_config.types_path);
@@ -100,13 +321,84 @@ pub fn write_content_to_interface_path(
         file_path.push(p);
     }
 
-    let dir_path = file_path.parent().unwrap();
+    Ok(file_path)
+}
+
+// 接口路径对应的 ts、schema 与 dart 文件，顺序固定，不判断文件是否存在
+pub fn get_generated_file_paths(path: &str, source_path: &str) -> Result<Vec<PathBuf>, io::Error> {
+    let project_config = get_project_config(source_path)?;
+    let mut file_paths = vec![
+        get_ts_file_path(path.to_string(), source_path)?,
+        get_schema_file_path(path.to_string(), source_path)?,
+    ];
+
+    if is_dart_enabled(&project_config) {
+        file_paths.push(get_dart_file_path(path.to_string(), source_path)?);
+    }
+
+    Ok(file_paths)
+}
 
-    fs::create_dir_all(dir_path).unwrap();
+// 在 go_path 的各个包目录中查找接口路径对应的 go 文件
+pub fn find_go_file_path(path: &str, source_path: &str) -> Result<Option<PathBuf>, io::Error> {
+    let go_path = match get_project_config(source_path)?.go_path {
+        Some(go_path) if !go_path.is_empty() => PathBuf::from(source_path).join(go_path),
+        _ => return Ok(None),
+    };
+    if !go_path.exists() {
+        return Ok(None);
+    }
 
-    let file_full_name = format!("{}.ts", file_path.to_str().unwrap().to_string());
+    let file_name = format!("{}.go", go_resolver::get_go_file_name(path.to_string()));
+    for entry in fs::read_dir(go_path)? {
+        let file_path = entry?.path().join(&file_name);
+        if file_path.exists() {
+            return Ok(Some(file_path));
+        }
+    }
 
-    fs::write(file_full_name, content).unwrap();
+    Ok(None)
+}
 
-    Ok(())
+// dart_path 下的 dart 文件，文件名使用下划线风格
+pub fn get_dart_file_path(path: String, source_path: &str) -> Result<PathBuf, io::Error> {
+    let project_config = get_project_config(source_path)?;
+    let dart_path = match project_config.dart_path {
+        Some(dart_path) if !dart_path.is_empty() => dart_path,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "未配置 dart 文件路径",
+            ))
+        }
+    };
+    let mut file_path = PathBuf::from(source_path).join(dart_path);
+
+    for p in dart_resolver::get_dart_file_path_arr(path) {
+        file_path.push(p);
+    }
+
+    Ok(PathBuf::from(format!("{}.dart", file_path.to_string_lossy())))
+}
+
+// go_path 下的 go 文件，go 要求同一目录只有一个包，因此按包名分目录
+pub fn get_go_file_path(
+    path: String,
+    source_path: &str,
+    content: &str,
+) -> Result<PathBuf, io::Error> {
+    let project_config = get_project_config(source_path)?;
+    let go_path = match project_config.go_path {
+        Some(go_path) if !go_path.is_empty() => go_path,
+        _ => return Err(io::Error::new(io::ErrorKind::Other, "未配置 go 文件路径")),
+    };
+    let package_name = match go_resolver::get_package_name_from_content(content) {
+        Some(package_name) => package_name,
+        None => return Err(io::Error::new(io::ErrorKind::Other, "go 文件内容缺少包名")),
+    };
+
+    Ok(PathBuf::from(source_path)
+        .join(go_path)
+        .join(package_name)
+        .join(format!("{}.go", go_resolver::get_go_file_name(path))))
 }
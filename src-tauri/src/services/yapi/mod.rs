@@ -2,4 +2,12 @@ pub mod config;
 pub mod project;
 pub mod category;
 pub mod interface;
-pub mod resolver;
\ No newline at end of file
+pub mod resolver;
+pub mod client;
+pub mod cache;
+pub mod provenance;
+pub mod diff;
+pub mod orphan;
+pub mod rename;
+pub mod history;
+pub mod check;
\ No newline at end of file
@@ -0,0 +1,314 @@
+use std::{fs, io, path::PathBuf};
+
+use crate::models::yapi::project::{InterfaceMove, OrphanAction, ReconcileReport};
+
+use super::{
+    config::{get_interface_lock_reason, get_project_config, write_project_config},
+    interface::{find_go_file_path, get_generated_file_paths},
+    provenance::{is_manually_edited, restamp_provenance},
+    resolver::go_resolver,
+};
+
+const DEPRECATED_REASON: &str = "yapi 中已删除该接口";
+
+// 处理项目中标记为孤立的接口：删除生成的文件，或把其中的类型标记为废弃
+// 锁定的接口与生成后被手动修改过的文件只有 force 为 true 时才会处理
+pub fn resolve_orphaned_interfaces(
+    source_path: &str,
+    project_id: &str,
+    action: &OrphanAction,
+    force: bool,
+) -> Result<ReconcileReport, io::Error> {
+    let mut yapi_config = get_project_config(source_path)?;
+    let project = yapi_config
+        .project_list
+        .iter()
+        .find(|project| project.project_id == project_id)
+        .ok_or(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("项目 {} 不存在", project_id),
+        ))?;
+    let mut report = ReconcileReport::default();
+    // 已处理的接口，删除时从 yapi.json 中移除
+    let mut resolved_ids = vec![];
+
+    for interface in project
+        .categories
+        .iter()
+        .flat_map(|category| &category.interfaces)
+    {
+        if interface.orphaned != Some(true) {
+            continue;
+        }
+        let path = match &interface.path {
+            Some(path) => path,
+            None => continue,
+        };
+
+        let mut file_paths = get_generated_file_paths(path, source_path)?;
+        file_paths.extend(find_go_file_path(path, source_path)?);
+        file_paths.retain(|file_path| file_path.exists());
+
+        // 接口的文件要么全部处理，要么全部跳过
+        let lock_reason = get_interface_lock_reason(&yapi_config, Some(&interface.id), None);
+        let skipped = get_skipped_files(&file_paths, lock_reason.as_deref(), force)?;
+        if !skipped.is_empty() {
+            report.skipped.extend(skipped);
+            continue;
+        }
+        if let (Some(lock_reason), false) = (&lock_reason, force) {
+            report
+                .skipped
+                .push(format!("{}：{}", interface.name, lock_reason));
+            continue;
+        }
+
+        for file_path in file_paths {
+            match action {
+                OrphanAction::Delete => fs::remove_file(&file_path)?,
+                OrphanAction::Deprecate => {
+                    if !mark_file_deprecated(&file_path)? {
+                        report
+                            .skipped
+                            .push(format!("{}：已标记为废弃", file_path.to_string_lossy()));
+                        continue;
+                    }
+                }
+            }
+            report.files.push(file_path.to_string_lossy().to_string());
+        }
+        resolved_ids.push(interface.id.clone());
+    }
+
+    if *action == OrphanAction::Delete {
+        if let Some(project) = yapi_config
+            .project_list
+            .iter_mut()
+            .find(|project| project.project_id == project_id)
+        {
+            // 分类下的接口都已删除时，分类本身也已删除
+            project.categories.retain_mut(|category| {
+                let is_empty = category.interfaces.is_empty();
+                category
+                    .interfaces
+                    .retain(|interface| !resolved_ids.contains(&interface.id));
+                is_empty || !category.interfaces.is_empty()
+            });
+        }
+        write_project_config(source_path, yapi_config)?;
+    }
+
+    Ok(report)
+}
+
+// 锁定的接口与生成后被手动修改过的文件不处理，返回跳过的文件及原因
+fn get_skipped_files(
+    file_paths: &[PathBuf],
+    lock_reason: Option<&str>,
+    force: bool,
+) -> Result<Vec<String>, io::Error> {
+    let mut skipped = vec![];
+    if force {
+        return Ok(skipped);
+    }
+
+    for file_path in file_paths {
+        let reason = match lock_reason {
+            Some(lock_reason) => lock_reason.to_string(),
+            None if is_manually_edited(&fs::read_to_string(file_path)?) => {
+                String::from("生成后被手动修改过")
+            }
+            None => continue,
+        };
+        skipped.push(format!("{}：{}", file_path.to_string_lossy(), reason));
+    }
+
+    Ok(skipped)
+}
+
+// 只有内容与来源信息一致时才重新计算哈希，手动修改过的文件保留原哈希以便继续识别
+fn get_restamped_content(original: &str, content: &str) -> String {
+    if is_manually_edited(original) {
+        content.to_string()
+    } else {
+        restamp_provenance(content)
+    }
+}
+
+// 在类型声明前加上废弃标记，返回文件是否被修改
+fn mark_file_deprecated(file_path: &PathBuf) -> Result<bool, io::Error> {
+    let content = fs::read_to_string(file_path)?;
+    if content.contains(DEPRECATED_REASON) {
+        return Ok(false);
+    }
+
+    let extension = file_path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut res_string = String::new();
+
+    for line in content.split_inclusive('\n') {
+        let marker = match extension.as_str() {
+            "ts" if line.starts_with("export interface ")
+                || line.starts_with("export const ")
+                || line.starts_with("export type ") =>
+            {
+                Some(format!("/** @deprecated {} */\n", DEPRECATED_REASON))
+            }
+            "dart" if line.starts_with("class ") => {
+                Some(format!("@Deprecated('{}')\n", DEPRECATED_REASON))
+            }
+            "go" if line.starts_with("type ") => {
+                Some(format!("//\n// Deprecated: {}\n", DEPRECATED_REASON))
+            }
+            _ => None,
+        };
+
+        if let Some(marker) = marker {
+            res_string += &marker;
+        }
+        res_string += line;
+    }
+
+    fs::write(file_path, get_restamped_content(&content, &res_string))?;
+    Ok(true)
+}
+
+// 把移动或改变路径的接口已生成的文件移到新位置，go 文件同时更新包名
+// 锁定的接口与生成后被手动修改过的文件只有 force 为 true 时才会移动
+pub fn relocate_moved_interfaces(
+    source_path: &str,
+    project_id: &str,
+    moves: &[InterfaceMove],
+    force: bool,
+) -> Result<ReconcileReport, io::Error> {
+    let project_config = get_project_config(source_path)?;
+    let project = project_config
+        .project_list
+        .iter()
+        .find(|project| project.project_id == project_id)
+        .ok_or(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("项目 {} 不存在", project_id),
+        ))?;
+    let mut report = ReconcileReport::default();
+
+    for interface_move in moves {
+        let (old_path, new_path) = match (&interface_move.old_path, &interface_move.new_path) {
+            (Some(old_path), Some(new_path)) => (old_path, new_path),
+            _ => continue,
+        };
+
+        let old_go_file_path = find_go_file_path(old_path, source_path)?;
+        let mut old_file_paths = get_generated_file_paths(old_path, source_path)?;
+        old_file_paths.extend(old_go_file_path.clone());
+        old_file_paths.retain(|file_path| file_path.exists());
+
+        // 接口的文件要么全部移动，要么全部跳过
+        let lock_reason =
+            get_interface_lock_reason(&project_config, Some(&interface_move.id), None);
+        let skipped = get_skipped_files(&old_file_paths, lock_reason.as_deref(), force)?;
+        if !skipped.is_empty() {
+            report.skipped.extend(skipped);
+            continue;
+        }
+
+        if old_path != new_path {
+            let old_file_paths = get_generated_file_paths(old_path, source_path)?;
+            let new_file_paths = get_generated_file_paths(new_path, source_path)?;
+
+            for (old_file_path, new_file_path) in old_file_paths.iter().zip(new_file_paths.iter()) {
+                if !old_file_path.exists() {
+                    continue;
+                }
+                if new_file_path.exists() {
+                    report.skipped.push(format!(
+                        "{}：目标文件已存在",
+                        new_file_path.to_string_lossy()
+                    ));
+                    continue;
+                }
+                move_file(old_file_path, new_file_path, None)?;
+                report
+                    .files
+                    .push(new_file_path.to_string_lossy().to_string());
+            }
+        }
+
+        // go 文件按分类生成包名，分类或路径变化时都需要移动
+        if let Some(old_go_file_path) = old_go_file_path {
+            let category_name = project
+                .categories
+                .iter()
+                .find(|category| category.id == interface_move.new_cat_id)
+                .map(|category| category.name.as_str());
+            let package_name = go_resolver::get_go_package_name(
+                category_name,
+                new_path,
+                interface_move.new_cat_id.parse().unwrap_or_default(),
+            );
+            let new_go_file_path =
+                old_go_file_path
+                    .parent()
+                    .and_then(|dir| dir.parent())
+                    .map(|go_path| {
+                        go_path.join(&package_name).join(format!(
+                            "{}.go",
+                            go_resolver::get_go_file_name(new_path.clone())
+                        ))
+                    });
+
+            match new_go_file_path {
+                Some(new_go_file_path) if new_go_file_path == old_go_file_path => {}
+                Some(new_go_file_path) if new_go_file_path.exists() => {
+                    report.skipped.push(format!(
+                        "{}：目标文件已存在",
+                        new_go_file_path.to_string_lossy()
+                    ));
+                }
+                Some(new_go_file_path) => {
+                    move_file(&old_go_file_path, &new_go_file_path, Some(&package_name))?;
+                    report
+                        .files
+                        .push(new_go_file_path.to_string_lossy().to_string());
+                }
+                None => {}
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+// 移动文件，指定包名时替换 go 文件的 package 声明
+fn move_file(
+    old_file_path: &PathBuf,
+    new_file_path: &PathBuf,
+    package_name: Option<&str>,
+) -> Result<(), io::Error> {
+    if let Some(dir_path) = new_file_path.parent() {
+        fs::create_dir_all(dir_path)?;
+    }
+
+    match package_name {
+        Some(package_name) => {
+            let original = fs::read_to_string(old_file_path)?;
+            let content: String = original
+                .split_inclusive('\n')
+                .map(|line| {
+                    if line.starts_with("package ") {
+                        format!("package {}\n", package_name)
+                    } else {
+                        line.to_string()
+                    }
+                })
+                .collect();
+            fs::write(new_file_path, get_restamped_content(&original, &content))?;
+            fs::remove_file(old_file_path)?;
+        }
+        None => fs::rename(old_file_path, new_file_path)?,
+    }
+
+    Ok(())
+}
@@ -4,41 +4,43 @@ use tauri::AppHandle;
 
 use crate::{
     models::yapi::{
-        category::CategoryMenuItem, project::YapiProjectBaseInfo, web_response::YapiResponse,
+        category::{CategoryMenuItem, MenuCategory},
+        config::YapiServer,
+        project::YapiProjectBaseInfo,
     },
-    services::reqwest::{get_data, get_reqwest_client},
+    services::reqwest::{get_reqwest_client, get_retry_policy},
 };
 
-use super::config::get_project_config;
+use super::{client::get_yapi_data, config::get_yapi_server};
 
 const PROJECT_BASE_INFO_API: &str = "api/project/get";
 const CATEGORY_MENU_API: &str = "api/interface/getCatMenu";
+const INTERFACE_MENU_API: &str = "api/interface/list_menu";
 
+// server 用于添加其他 yapi 上的项目，为空时按 token 查找已配置的项目
 pub async fn fetch_project_base_info(
     token: String,
     source_path: &str,
+    server: Option<YapiServer>,
     app_handle: &AppHandle,
 ) -> Result<YapiProjectBaseInfo, io::Error> {
-    let mut project_config = get_project_config(source_path)?;
-    if !project_config.base_url.ends_with('/') {
-        project_config.base_url.push('/');
-    }
+    let server = match server {
+        Some(server) => server,
+        None => get_yapi_server(source_path, &token, None)?,
+    };
 
-    let url = format!(
-        "{}{}?token={}",
-        project_config.base_url, PROJECT_BASE_INFO_API, token
-    );
-    let client = get_reqwest_client(&app_handle)?;
+    let url = format!("{}{}?token={}", server.base_url, PROJECT_BASE_INFO_API, token);
+    let client = get_reqwest_client(app_handle, server.proxy.as_deref())?;
 
     // notification(&app_handle, NotificationDesc::Success, "获取项目信息中...");
 
-    match get_data::<YapiResponse<YapiProjectBaseInfo>>(client, url).await {
-        Ok(res) => Ok(res.data),
-        Err(e) => Err(io::Error::new(
-            io::ErrorKind::Other,
-            format!("获取项目信息失败: {}", e.to_string()),
-        )),
-    }
+    let retry_policy = get_retry_policy(app_handle)?;
+
+    Ok(
+        get_yapi_data::<YapiProjectBaseInfo>(client, url, "获取项目信息", &retry_policy)
+            .await?
+            .value,
+    )
 }
 
 pub async fn fetch_project_cat_menu(
@@ -47,23 +49,43 @@ pub async fn fetch_project_cat_menu(
     source_path: &str,
     app_handle: &AppHandle,
 ) -> Result<Vec<CategoryMenuItem>, io::Error> {
-    let client = get_reqwest_client(&app_handle)?;
-    let mut project_config = get_project_config(source_path)?;
+    let server = get_yapi_server(source_path, &token, Some(&project_id.to_string()))?;
+    let client = get_reqwest_client(app_handle, server.proxy.as_deref())?;
+
+    let url = format!(
+        "{}{}?project_id={}&token={}",
+        server.base_url, CATEGORY_MENU_API, project_id, token
+    );
 
-    if !project_config.base_url.ends_with('/') {
-        project_config.base_url.push('/');
-    }
+    let retry_policy = get_retry_policy(app_handle)?;
+
+    Ok(
+        get_yapi_data::<Vec<CategoryMenuItem>>(client, url, "获取分类列表", &retry_policy)
+            .await?
+            .value,
+    )
+}
+
+// 一次获取项目的全部分类及其下的接口
+pub async fn fetch_project_menu(
+    project_id: u32,
+    token: String,
+    source_path: &str,
+    app_handle: &AppHandle,
+) -> Result<Vec<MenuCategory>, io::Error> {
+    let server = get_yapi_server(source_path, &token, Some(&project_id.to_string()))?;
+    let client = get_reqwest_client(app_handle, server.proxy.as_deref())?;
 
     let url = format!(
         "{}{}?project_id={}&token={}",
-        project_config.base_url, CATEGORY_MENU_API, project_id, token
+        server.base_url, INTERFACE_MENU_API, project_id, token
     );
 
-    match get_data::<YapiResponse<Vec<CategoryMenuItem>>>(client, url).await {
-        Ok(res) => Ok(res.data),
-        Err(err) => Err(io::Error::new(
-            io::ErrorKind::Other,
-            format!("获取分类列表失败: {}", err.to_string()),
-        )),
-    }
+    let retry_policy = get_retry_policy(app_handle)?;
+
+    Ok(
+        get_yapi_data::<Vec<MenuCategory>>(client, url, "获取项目接口目录", &retry_policy)
+            .await?
+            .value,
+    )
 }
@@ -0,0 +1,110 @@
+use std::{fs, io, path::Path};
+
+use crate::{
+    models::yapi::{config::ManualEditPolicy, interface::InterfaceData, provenance::Provenance},
+    services::conversion::string_to_content_hash,
+};
+
+// 生成文件的首行，ts、dart 与 go 都使用 // 注释
+pub const PROVENANCE_PREFIX: &str = "// @yapi-to-ts ";
+
+// 在生成的内容前加上来源信息，哈希只计算首行之后的内容
+pub fn stamp_provenance(content: String, data: &InterfaceData) -> String {
+    let up_time = data
+        .up_time
+        .map_or(String::from("-"), |up_time| up_time.to_string());
+
+    format!(
+        "{}project_id={} interface_id={} up_time={} version={} hash={}\n{}",
+        PROVENANCE_PREFIX,
+        data.project_id,
+        data._id,
+        up_time,
+        env!("CARGO_PKG_VERSION"),
+        get_body_hash(&content),
+        content
+    )
+}
+
+// 工具修改生成的文件后（如标记废弃）重新计算哈希，避免被误判为手动修改
+pub fn restamp_provenance(content: &str) -> String {
+    match parse_provenance(content) {
+        Some((provenance, body)) => {
+            let up_time = provenance
+                .up_time
+                .map_or(String::from("-"), |up_time| up_time.to_string());
+            format!(
+                "{}project_id={} interface_id={} up_time={} version={} hash={}\n{}",
+                PROVENANCE_PREFIX,
+                provenance.project_id,
+                provenance.interface_id,
+                up_time,
+                provenance.version,
+                get_body_hash(body),
+                body
+            )
+        }
+        None => content.to_string(),
+    }
+}
+
+// 哈希前统一换行符，git 在 Windows 上签出为 CRLF 时不视为手动修改
+fn get_body_hash(body: &str) -> String {
+    string_to_content_hash(&body.replace("\r\n", "\n"))
+}
+
+pub fn is_provenance_line(line: &str) -> bool {
+    line.starts_with(PROVENANCE_PREFIX)
+}
+
+// 解析首行的来源信息，返回来源信息与其后的内容
+pub fn parse_provenance(content: &str) -> Option<(Provenance, &str)> {
+    let (first_line, body) = content.split_once('\n')?;
+    let fields = first_line.strip_prefix(PROVENANCE_PREFIX)?;
+    let get_field = |name: &str| {
+        fields
+            .split_whitespace()
+            .find_map(|field| field.strip_prefix(name)?.strip_prefix('='))
+    };
+
+    let provenance = Provenance {
+        project_id: get_field("project_id")?.parse().ok()?,
+        interface_id: get_field("interface_id")?.parse().ok()?,
+        up_time: get_field("up_time").and_then(|up_time| up_time.parse().ok()),
+        version: get_field("version")?.to_string(),
+        hash: get_field("hash")?.to_string(),
+    };
+
+    Some((provenance, body))
+}
+
+// 文件生成后是否被手动修改，没有来源信息的文件无法判断
+pub fn is_manually_edited(content: &str) -> bool {
+    match parse_provenance(content) {
+        Some((provenance, body)) => get_body_hash(body) != provenance.hash,
+        None => false,
+    }
+}
+
+// 覆盖文件前检查是否被手动修改，按配置返回提示或拒绝写入
+pub fn check_manual_edit(
+    file_path: &Path,
+    policy: &ManualEditPolicy,
+    force: bool,
+) -> Result<Option<String>, io::Error> {
+    if *policy == ManualEditPolicy::Overwrite || !file_path.exists() {
+        return Ok(None);
+    }
+    if !is_manually_edited(&fs::read_to_string(file_path)?) {
+        return Ok(None);
+    }
+
+    let message = format!("文件 {} 生成后被手动修改过", file_path.to_string_lossy());
+    match policy {
+        ManualEditPolicy::Refuse if !force => Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("{}，如需覆盖请强制写入", message),
+        )),
+        _ => Ok(Some(format!("{}，已覆盖", message))),
+    }
+}
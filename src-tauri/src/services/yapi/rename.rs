@@ -0,0 +1,410 @@
+use std::{fs, io, path::PathBuf};
+
+use crate::models::yapi::{
+    config::YapiConfig,
+    project::{PathRename, PathRenameReport},
+};
+
+use super::{
+    config::{get_interface_lock_reason, get_project_config, write_project_config},
+    interface::{find_go_file_path, get_generated_file_paths},
+    provenance::{is_manually_edited, restamp_provenance},
+    resolver::{
+        common::{capitalize_first_letter, get_legal_name, get_path_arr},
+        go_resolver,
+    },
+};
+
+// 可能 import 类型文件的请求文件
+const REQUEST_FILE_EXTENSIONS: [&str; 7] = ["ts", "tsx", "mts", "js", "jsx", "vue", "svelte"];
+
+// 生成文件中声明类型、schema 与转换函数的关键字
+const DECLARATION_KEYWORDS: [&str; 5] = ["interface", "type", "const", "class", "function"];
+
+// 以 yapi 接口 id 为准，找出路径与上次生成时不同的接口
+pub fn get_path_renames(yapi_config: &YapiConfig, project_id: Option<&str>) -> Vec<PathRename> {
+    yapi_config
+        .project_list
+        .iter()
+        .filter(|project| project_id.map_or(true, |project_id| project.project_id == project_id))
+        .flat_map(|project| &project.categories)
+        .flat_map(|category| &category.interfaces)
+        .filter_map(
+            |interface| match (&interface.generated_path, &interface.path) {
+                (Some(old_path), Some(new_path)) if old_path != new_path => Some(PathRename {
+                    id: interface.id.clone(),
+                    name: interface.name.clone(),
+                    old_path: old_path.clone(),
+                    new_path: new_path.clone(),
+                }),
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+// 移动改名接口的生成文件，并更新 request_path 下引用旧文件的 import
+// 锁定的接口只有 force 为 true 时才会处理
+pub fn relocate_renamed_interfaces(
+    source_path: &str,
+    project_id: Option<&str>,
+    force: bool,
+) -> Result<PathRenameReport, io::Error> {
+    let mut yapi_config = get_project_config(source_path)?;
+    let mut report = PathRenameReport {
+        renames: get_path_renames(&yapi_config, project_id),
+        ..Default::default()
+    };
+    let request_files =
+        get_request_files(&PathBuf::from(source_path).join(&yapi_config.request_path))?;
+
+    let mut locked_ids = vec![];
+    for rename in &report.renames {
+        if let (Some(lock_reason), false) = (
+            get_interface_lock_reason(&yapi_config, Some(&rename.id), None),
+            force,
+        ) {
+            report
+                .skipped
+                .push(format!("{}：{}", rename.name, lock_reason));
+            locked_ids.push(rename.id.clone());
+        }
+    }
+    report
+        .renames
+        .retain(|rename| !locked_ids.contains(&rename.id));
+
+    for rename in &report.renames.clone() {
+        move_generated_files(source_path, rename, &mut report)?;
+
+        for request_file in &request_files {
+            let content = fs::read_to_string(request_file)?;
+            if let Some(content) = rewrite_imports(&content, &rename.old_path, &rename.new_path) {
+                fs::write(request_file, content)?;
+                let request_file = request_file.to_string_lossy().to_string();
+                if !report.rewritten.contains(&request_file) {
+                    report.rewritten.push(request_file);
+                }
+            }
+        }
+    }
+
+    // 处理完成后以新路径作为生成路径
+    for interface in yapi_config
+        .project_list
+        .iter_mut()
+        .flat_map(|project| project.categories.iter_mut())
+        .flat_map(|category| category.interfaces.iter_mut())
+    {
+        if report
+            .renames
+            .iter()
+            .any(|rename| rename.id == interface.id)
+        {
+            interface.generated_path = interface.path.clone();
+        }
+    }
+    write_project_config(source_path, yapi_config)?;
+
+    Ok(report)
+}
+
+fn move_generated_files(
+    source_path: &str,
+    rename: &PathRename,
+    report: &mut PathRenameReport,
+) -> Result<(), io::Error> {
+    let mut file_pairs: Vec<_> = get_generated_file_paths(&rename.old_path, source_path)?
+        .into_iter()
+        .zip(get_generated_file_paths(&rename.new_path, source_path)?)
+        .collect();
+
+    // go 文件保持在原包目录中，只修改文件名
+    if let Some(old_go_file_path) = find_go_file_path(&rename.old_path, source_path)? {
+        let new_go_file_path = old_go_file_path.with_file_name(format!(
+            "{}.go",
+            go_resolver::get_go_file_name(rename.new_path.clone())
+        ));
+        file_pairs.push((old_go_file_path, new_go_file_path));
+    }
+
+    for (old_file_path, new_file_path) in file_pairs {
+        if !old_file_path.exists() || old_file_path == new_file_path {
+            continue;
+        }
+
+        // 新路径已经生成过时，旧文件已无用，手动修改过的除外
+        if new_file_path.exists() {
+            if is_manually_edited(&fs::read_to_string(&old_file_path)?) {
+                report.skipped.push(format!(
+                    "{}：文件被手动修改过，且新文件已存在",
+                    old_file_path.to_string_lossy()
+                ));
+            } else {
+                fs::remove_file(&old_file_path)?;
+            }
+            continue;
+        }
+
+        if let Some(dir_path) = new_file_path.parent() {
+            fs::create_dir_all(dir_path)?;
+        }
+        // 文件中的类型名由文件名生成，移动后同步改名，保证与更新后的 import 一致
+        let content = fs::read_to_string(&old_file_path)?;
+        let renamed_content = rename_declarations(&content, &rename.old_path, &rename.new_path);
+        let renamed_content = if is_manually_edited(&content) {
+            renamed_content
+        } else {
+            restamp_provenance(&renamed_content)
+        };
+        fs::write(&new_file_path, renamed_content)?;
+        fs::remove_file(&old_file_path)?;
+        report
+            .moved
+            .push(new_file_path.to_string_lossy().to_string());
+    }
+
+    Ok(())
+}
+
+fn get_request_files(dir: &PathBuf) -> Result<Vec<PathBuf>, io::Error> {
+    let mut files = vec![];
+    if !dir.is_dir() {
+        return Ok(files);
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        if file_name.starts_with('.') || file_name == "node_modules" {
+            continue;
+        }
+
+        if path.is_dir() {
+            files.extend(get_request_files(&path)?);
+        } else if path.extension().map_or(false, |extension| {
+            REQUEST_FILE_EXTENSIONS.contains(&extension.to_string_lossy().as_ref())
+        }) {
+            files.push(path);
+        }
+    }
+
+    Ok(files)
+}
+
+// 替换 import 中指向旧类型文件的路径，以及从旧文件引入的、由文件名生成的类型名
+// 没有引用旧文件时返回 None
+pub fn rewrite_imports(content: &str, old_path: &str, new_path: &str) -> Option<String> {
+    let old_path_arr = get_path_arr(old_path.to_string());
+    let new_path_arr = get_path_arr(new_path.to_string());
+    let old_module = old_path_arr.join("/");
+    let new_module = new_path_arr.join("/");
+    if old_module.is_empty() || old_module == new_module {
+        return None;
+    }
+
+    let lines: Vec<_> = content.split_inclusive('\n').collect();
+    let mut res_string = String::new();
+    let mut imported_names: Vec<String> = vec![];
+    let mut is_rewritten = false;
+
+    for (index, line) in lines.iter().enumerate() {
+        let rewritten_line = if is_import_line(line) {
+            rewrite_specifiers(line, &old_module, &new_module)
+        } else {
+            None
+        };
+        match rewritten_line {
+            Some(rewritten_line) => {
+                // 多行 import 从 import 或 export 所在的行开始
+                let start = lines[..=index]
+                    .iter()
+                    .rposition(|line| {
+                        let line = line.trim_start();
+                        line.starts_with("import") || line.starts_with("export")
+                    })
+                    .unwrap_or(index);
+                for name in get_identifiers(&lines[start..=index].concat()) {
+                    if !imported_names.contains(&name) {
+                        imported_names.push(name);
+                    }
+                }
+                res_string += &rewritten_line;
+                is_rewritten = true;
+            }
+            None => res_string += line,
+        }
+    }
+
+    if !is_rewritten {
+        return None;
+    }
+
+    let old_name = get_legal_name(old_path_arr.last()?);
+    let new_name = get_legal_name(new_path_arr.last()?);
+    for name in imported_names {
+        if let Some(renamed) = swap_name_prefix(&name, &old_name, &new_name) {
+            res_string = replace_identifier(&res_string, &name, &renamed);
+        }
+    }
+
+    Some(res_string)
+}
+
+// 把生成文件中声明的、由旧文件名生成的名称替换为由新文件名生成的名称
+pub fn rename_declarations(content: &str, old_path: &str, new_path: &str) -> String {
+    let old_name = get_path_arr(old_path.to_string())
+        .last()
+        .map(|name| get_legal_name(name))
+        .unwrap_or_default();
+    let new_name = get_path_arr(new_path.to_string())
+        .last()
+        .map(|name| get_legal_name(name))
+        .unwrap_or_default();
+    // go 的结构体名以完整路径为前缀
+    let name_pairs = [
+        (old_name, new_name),
+        (
+            go_resolver::get_go_struct_prefix(old_path),
+            go_resolver::get_go_struct_prefix(new_path),
+        ),
+    ];
+    let identifiers = get_identifiers(content);
+    let mut res_string = content.to_string();
+    let mut renamed_names = vec![];
+
+    for pair in identifiers.windows(2) {
+        if !DECLARATION_KEYWORDS.contains(&pair[0].as_str()) || renamed_names.contains(&pair[1]) {
+            continue;
+        }
+        let renamed = name_pairs
+            .iter()
+            .find_map(|(old_name, new_name)| swap_name_prefix(&pair[1], old_name, new_name));
+        if let Some(renamed) = renamed {
+            res_string = replace_identifier(&res_string, &pair[1], &renamed);
+            renamed_names.push(pair[1].clone());
+        }
+    }
+
+    res_string
+}
+
+// 类型名由文件名与首字母大写的属性名拼接而成（见 get_ts_interface_name），
+// dart、go 的类名与请求响应的转换函数使用首字母大写的形式
+fn swap_name_prefix(name: &str, old_name: &str, new_name: &str) -> Option<String> {
+    if old_name.is_empty() || old_name == new_name {
+        return None;
+    }
+    let forms = [
+        (old_name.to_string(), new_name.to_string()),
+        (
+            capitalize_first_letter(old_name),
+            capitalize_first_letter(new_name),
+        ),
+    ];
+
+    for prefix in ["", "to", "from"] {
+        for (old_form, new_form) in &forms {
+            let rest = match name
+                .strip_prefix(prefix)
+                .and_then(|name| name.strip_prefix(old_form.as_str()))
+            {
+                Some(rest) => rest,
+                None => continue,
+            };
+            if rest.starts_with(|c: char| c.is_ascii_uppercase() || c.is_ascii_digit()) {
+                return Some(format!("{}{}{}", prefix, new_form, rest));
+            }
+        }
+    }
+
+    None
+}
+
+fn get_identifiers(content: &str) -> Vec<String> {
+    content
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$'))
+        .filter(|identifier| {
+            identifier
+                .chars()
+                .next()
+                .map_or(false, |c| !c.is_ascii_digit())
+        })
+        .map(String::from)
+        .collect()
+}
+
+fn is_import_line(line: &str) -> bool {
+    let line = line.trim_start();
+    ((line.starts_with("import") || line.starts_with("export")) && line.contains("from"))
+        || line.contains("import(")
+        || line.contains("require(")
+        || line.starts_with("} from")
+}
+
+// 替换一行中以旧模块路径结尾的字符串
+fn rewrite_specifiers(line: &str, old_module: &str, new_module: &str) -> Option<String> {
+    let mut res_string = String::new();
+    let mut rest = line;
+    let mut is_rewritten = false;
+
+    while let Some(start) = rest.find(|c| c == '\'' || c == '"') {
+        let quote = rest[start..].chars().next()?;
+        let end = match rest[start + 1..].find(quote) {
+            Some(end) => start + 1 + end,
+            None => break,
+        };
+        let specifier = &rest[start + 1..end];
+        let (module, extension) = match specifier.rsplit_once('.') {
+            Some((module, extension)) if ["ts", "js"].contains(&extension) => {
+                (module, format!(".{}", extension))
+            }
+            _ => (specifier, String::new()),
+        };
+        // zod 的 schema 文件与类型文件同名，多一个 .schema 后缀
+        let (module, schema) = match module.strip_suffix(".schema") {
+            Some(module) => (module, ".schema"),
+            None => (module, ""),
+        };
+
+        res_string += &rest[..start + 1];
+        match module.strip_suffix(old_module) {
+            Some(prefix) if prefix.is_empty() || prefix.ends_with('/') => {
+                res_string += &format!("{}{}{}{}", prefix, new_module, schema, extension);
+                is_rewritten = true;
+            }
+            _ => res_string += specifier,
+        }
+        res_string.push(quote);
+        rest = &rest[end + 1..];
+    }
+
+    if is_rewritten {
+        Some(res_string + rest)
+    } else {
+        None
+    }
+}
+
+// 只替换完整的标识符
+fn replace_identifier(content: &str, old: &str, new: &str) -> String {
+    let is_identifier_char = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '$';
+    let mut res_string = String::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find(old) {
+        let end = start + old.len();
+        let before = rest[..start].chars().next_back();
+        let after = rest[end..].chars().next();
+
+        res_string += &rest[..start];
+        if before.map_or(false, is_identifier_char) || after.map_or(false, is_identifier_char) {
+            res_string += old;
+        } else {
+            res_string += new;
+        }
+        rest = &rest[end..];
+    }
+
+    res_string + rest
+}
@@ -100,3 +100,40 @@ pub fn get_path_arr(raw_path: String) -> Vec<String> {
     path_arr
 }
 
+// 拼接生成ts接口名字
+pub fn get_ts_interface_name(interface_name: &str, key: &str) -> String {
+    format!(
+        "{}{}",
+        get_legal_name(interface_name),
+        capitalize_first_letter(&get_legal_name(key))
+    )
+}
+
+// 大写第一个字符
+pub fn capitalize_first_letter(s: &str) -> String {
+    let mut c = s.chars();
+    match c.next() {
+        None => String::new(),
+        Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
+    }
+}
+
+// 是否为合法的 ts 标识符
+pub fn is_ts_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+        }
+        _ => false,
+    }
+}
+
+// 对象字面量中的属性名，非法标识符需要加引号
+pub fn get_ts_property_key(key: &str) -> String {
+    if is_ts_identifier(key) {
+        key.to_string()
+    } else {
+        format!("\"{}\"", key)
+    }
+}
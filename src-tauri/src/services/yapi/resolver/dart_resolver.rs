@@ -0,0 +1,310 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::models::yapi::{
+    config::KeyCase,
+    interface::{Atom, JsonValue, ObjectLike, ObjectType, Root},
+};
+
+use super::{
+    common::{capitalize_first_letter, get_path_arr, get_ts_interface_name},
+    key_case::convert_key,
+};
+
+const DART_KEYWORDS: [&str; 33] = [
+    "assert", "break", "case", "catch", "class", "const", "continue", "default", "do", "else",
+    "enum", "extends", "false", "final", "finally", "for", "if", "in", "is", "new", "null",
+    "rethrow", "return", "super", "switch", "this", "throw", "true", "try", "var", "void",
+    "while", "with",
+];
+
+// 字段的 dart 类型
+enum DartType {
+    Atom(String),
+    Class(String),
+    List(Option<String>),
+    Map,
+}
+
+// 生成 dart 字符串，declared 记录已声明的类名与结构，同一文件内的请求与响应共用
+pub fn get_root_dart(root: &Root, declared: &mut HashMap<String, String>) -> String {
+    let class_name = get_dart_class_name(&root.interface_name, &root.key);
+    let mut res_string = format!("// {}\n", root.interface_desc);
+    let values = root.children.clone().unwrap_or_default();
+
+    resolve_classes(&values, &class_name, declared, &mut res_string);
+
+    res_string
+}
+
+// 接口路径转换为 dart 文件路径，文件名使用下划线风格
+pub fn get_dart_file_path_arr(raw_path: String) -> Vec<String> {
+    get_path_arr(raw_path)
+        .iter()
+        .map(|p| {
+            convert_key(p, &KeyCase::Snake)
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect()
+        })
+        .collect()
+}
+
+// 嵌套类先于引用它的类确定名称，输出时仍排在其后，返回实际声明的类名
+fn resolve_classes(
+    values: &[JsonValue],
+    class_name: &str,
+    declared: &mut HashMap<String, String>,
+    res_string: &mut String,
+) -> String {
+    let mut sub_string = String::new();
+    let mut fields = vec![];
+
+    for value in values {
+        let field = match value {
+            JsonValue::Atom(atom) => get_atom_field(atom),
+            JsonValue::ObjectLike(object_like) if object_like.nodes.is_empty() => {
+                get_object_like_field(object_like, None)
+            }
+            JsonValue::ObjectLike(object_like) => {
+                let sub_values: Vec<_> = object_like
+                    .nodes
+                    .iter()
+                    .map(|node| node.value.clone())
+                    .collect();
+                let sub_class_name = resolve_classes(
+                    &sub_values,
+                    &get_dart_class_name(&object_like.interface_name, &object_like.key),
+                    declared,
+                    &mut sub_string,
+                );
+                get_object_like_field(object_like, Some(sub_class_name))
+            }
+            JsonValue::Null => continue,
+        };
+        fields.push(field);
+    }
+    set_unique_field_names(&mut fields);
+
+    // 同名且结构相同的类只声明一次，结构不同时加上序号
+    let shape = get_class_shape(&fields);
+    let mut index = 1;
+    let class_name = loop {
+        let name = if index == 1 {
+            class_name.to_string()
+        } else {
+            format!("{}{}", class_name, index)
+        };
+        match declared.get(&name) {
+            Some(declared_shape) if *declared_shape == shape => return name,
+            Some(_) => index += 1,
+            None => break name,
+        }
+    };
+    declared.insert(class_name.clone(), shape);
+
+    *res_string += &get_class_dart(&class_name, &fields);
+    *res_string += &sub_string;
+
+    class_name
+}
+
+// 不同字段转换后可能同名，如 user_id 与 userId、_id 与 id，重复的加上序号
+fn set_unique_field_names(fields: &mut [DartField]) {
+    let mut names = HashSet::new();
+
+    for field in fields.iter_mut() {
+        let mut name = field.name.clone();
+        let mut index = 2;
+        while !names.insert(name.clone()) {
+            name = format!("{}{}", field.name, index);
+            index += 1;
+        }
+        field.name = name;
+    }
+}
+
+fn get_class_shape(fields: &[DartField]) -> String {
+    fields
+        .iter()
+        .map(|field| format!("{}: {}", field.json_key, get_field_type(field)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+struct DartField {
+    json_key: String,
+    name: String,
+    dart_type: DartType,
+    required: bool,
+    description: String,
+}
+
+fn get_atom_field(atom: &Atom) -> DartField {
+    let dart_type = match atom.value.as_str() {
+        "string" => "String",
+        "integer" => "int",
+        "number" => "num",
+        "boolean" => "bool",
+        _ => "dynamic",
+    };
+
+    DartField {
+        json_key: atom.key.clone(),
+        name: get_dart_field_name(&atom.key),
+        dart_type: DartType::Atom(dart_type.to_string()),
+        required: atom.required,
+        description: atom.description.replace('\n', ""),
+    }
+}
+
+// class_name 为嵌套对象实际声明的类名，没有子节点时为空
+fn get_object_like_field(object_like: &ObjectLike, class_name: Option<String>) -> DartField {
+    let dart_type = match (&object_like.object_type, class_name) {
+        (ObjectType::Array, class_name) => DartType::List(class_name),
+        (ObjectType::Object, Some(class_name)) => DartType::Class(class_name),
+        (ObjectType::Object, None) => DartType::Map,
+    };
+
+    DartField {
+        json_key: object_like.key.clone(),
+        name: get_dart_field_name(&object_like.key),
+        dart_type,
+        required: object_like.required,
+        description: object_like.description.clone(),
+    }
+}
+
+fn get_class_dart(class_name: &str, fields: &[DartField]) -> String {
+    let mut res_string = format!("class {} {{\n", class_name);
+
+    for field in fields {
+        res_string += &format!(
+            "  /// {}\n  final {} {};\n\n",
+            field.description,
+            get_field_type(field),
+            field.name
+        );
+    }
+
+    // 构造函数
+    if fields.is_empty() {
+        res_string += &format!("  const {}();\n\n", class_name);
+    } else {
+        res_string += &format!("  const {}({{\n", class_name);
+        for field in fields {
+            let required_symbol = if field.required { "required " } else { "" };
+            res_string += &format!("    {}this.{},\n", required_symbol, field.name);
+        }
+        res_string += "  });\n\n";
+    }
+
+    // fromJson
+    res_string += &format!(
+        "  factory {}.fromJson(Map<String, dynamic> json) => {}(\n",
+        class_name, class_name
+    );
+    for field in fields {
+        res_string += &format!("        {}: {},\n", field.name, get_from_json_expr(field));
+    }
+    res_string += "      );\n\n";
+
+    // toJson
+    res_string += "  Map<String, dynamic> toJson() => {\n";
+    for field in fields {
+        res_string += &format!(
+            "        {}: {},\n",
+            get_dart_string(&field.json_key),
+            get_to_json_expr(field)
+        );
+    }
+    res_string += "      };\n}\n\n";
+
+    res_string
+}
+
+fn get_field_type(field: &DartField) -> String {
+    let dart_type = match &field.dart_type {
+        DartType::Atom(t) => t.clone(),
+        DartType::Class(class_name) => class_name.clone(),
+        DartType::List(Some(class_name)) => format!("List<{}>", class_name),
+        DartType::List(None) => String::from("List<dynamic>"),
+        DartType::Map => String::from("Map<String, dynamic>"),
+    };
+
+    if field.required || dart_type == "dynamic" {
+        dart_type
+    } else {
+        format!("{}?", dart_type)
+    }
+}
+
+fn get_from_json_expr(field: &DartField) -> String {
+    let value = format!("json[{}]", get_dart_string(&field.json_key));
+    let nullable = if field.required { "" } else { "?" };
+
+    match &field.dart_type {
+        DartType::Atom(t) if t == "dynamic" => value,
+        DartType::Atom(t) if t == "int" => {
+            format!("({} as num{}){}.toInt()", value, nullable, nullable)
+        }
+        DartType::Atom(t) => format!("{} as {}{}", value, t, nullable),
+        DartType::Class(class_name) => {
+            let expr = format!("{}.fromJson({} as Map<String, dynamic>)", class_name, value);
+            if field.required {
+                expr
+            } else {
+                format!("{} == null ? null : {}", value, expr)
+            }
+        }
+        DartType::List(Some(class_name)) => format!(
+            "({} as List<dynamic>{}){}.map((e) => {}.fromJson(e as Map<String, dynamic>)).toList()",
+            value, nullable, nullable, class_name
+        ),
+        DartType::List(None) => format!("{} as List<dynamic>{}", value, nullable),
+        DartType::Map => format!("{} as Map<String, dynamic>{}", value, nullable),
+    }
+}
+
+fn get_to_json_expr(field: &DartField) -> String {
+    let nullable = if field.required { "" } else { "?" };
+
+    match &field.dart_type {
+        DartType::Class(_) => format!("{}{}.toJson()", field.name, nullable),
+        DartType::List(Some(_)) => format!(
+            "{}{}.map((e) => e.toJson()).toList()",
+            field.name, nullable
+        ),
+        _ => field.name.clone(),
+    }
+}
+
+// 与 ts 接口名规则一致，首字母大写
+fn get_dart_class_name(interface_name: &str, key: &str) -> String {
+    capitalize_first_letter(&get_ts_interface_name(interface_name, key))
+}
+
+// 下划线开头的字段在 dart 中为私有，不能作为命名参数
+fn get_dart_field_name(key: &str) -> String {
+    let name: String = convert_key(key, &KeyCase::Camel)
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .collect::<String>()
+        .trim_start_matches('_')
+        .to_string();
+
+    match name.chars().next() {
+        None => String::from("unknownName"),
+        Some(c) if c.is_ascii_digit() => format!("field{}", name),
+        Some(_) if DART_KEYWORDS.contains(&name.as_str()) => format!("{}_", name),
+        Some(_) => name,
+    }
+}
+
+fn get_dart_string(raw: &str) -> String {
+    format!(
+        "'{}'",
+        raw.replace('\\', "\\\\")
+            .replace('\'', "\\'")
+            .replace('$', "\\$")
+    )
+}
@@ -1,57 +1,100 @@
 use serde_json::Value;
 
-use crate::models::yapi::interface::{InterfaceData, WebType};
+use crate::models::yapi::{
+    config::KeyCase,
+    interface::{Atom, InterfaceData, JsonValue, Root, WebType},
+};
 
-use super::common::{get_desc, get_legal_name, get_path_arr};
+use super::{
+    common::{get_desc, get_legal_name, get_path_arr, get_ts_interface_name, get_ts_property_key},
+    key_case::convert_key,
+};
 
 pub fn get_ts_string(
     web_type: WebType,
     interface_data: &InterfaceData,
     form_value: &Value,
+    key_case: &KeyCase,
 ) -> String {
-    let header = format!("// {}", interface_data.title);
-    let file_name = get_path_arr(interface_data.path.clone())
-        .last()
-        .unwrap_or(&"unknowFileName".to_string())
-        .clone();
-    let interface_ts_name = get_interface_ts_name(web_type, &file_name);
-    let mut res_string = format!("{}\nexport interface {} {{\n", header, interface_ts_name);
+    let root = generate_root(web_type, interface_data, form_value);
+    get_root_ts(&root, key_case)
+}
 
-    // let form_value = json!(get_request_json(interface_data));
+pub fn get_root_ts(root: &Root, key_case: &KeyCase) -> String {
+    let header = format!("// {}", root.interface_desc);
+    let interface_ts_name = get_ts_interface_name(&root.interface_name, &root.key);
+    let mut res_string = format!("{}\nexport interface {} {{\n", header, interface_ts_name);
 
-    match form_value.is_array() {
-        true => {
-            for value in form_value.as_array().unwrap() {
-                let name = get_name(&value);
-                let t = get_type(&value);
-                let desc = get_desc(&value, "desc");
-                let required = is_required(&value);
-                let required_symbol = if required { "" } else { "?" };
+    for value in root.children.iter().flatten() {
+        if let JsonValue::Atom(atom) = value {
+            let name = get_field_name(&atom.key, key_case);
+            let required_symbol = if atom.required { "" } else { "?" };
 
-                let type_define: String =
-                    format!("    // {}\n    {}{}: {}\n", desc, name, required_symbol, t);
+            let type_define: String = format!(
+                "    // {}\n    {}{}: {}\n",
+                atom.description,
+                get_ts_property_key(&name),
+                required_symbol,
+                atom.value
+            );
 
-                res_string = res_string + type_define.as_str();
-            }
+            res_string = res_string + type_define.as_str();
         }
-        false => {}
     }
     res_string = res_string + "}\n";
 
     res_string
 }
 
-fn get_interface_ts_name(web_type: WebType, file_name: &str) -> String {
-    match web_type {
-        WebType::Request => format!("{}Request", get_legal_name(file_name)),
-        WebType::Response => format!("{}Response", get_legal_name(file_name)),
+// 生成根节点模型，表单字段均为原子节点，key 保留接口中的原始字段名
+pub fn generate_root(web_type: WebType, interface_data: &InterfaceData, form_value: &Value) -> Root {
+    let root_key = if web_type == WebType::Request {
+        String::from("request")
+    } else {
+        String::from("response")
+    };
+    let file_name = get_path_arr(interface_data.path.clone())
+        .last()
+        .unwrap_or(&"unknowFileName".to_string())
+        .clone();
+
+    let children = match form_value.as_array() {
+        Some(values) => values
+            .iter()
+            .map(|value| {
+                JsonValue::Atom(Atom {
+                    value: get_type(value),
+                    required: is_required(value),
+                    key: get_name(value),
+                    description: get_desc(value, "desc"),
+                    format: None,
+                })
+            })
+            .collect(),
+        None => vec![],
+    };
+
+    Root {
+        interface_name: file_name,
+        interface_desc: interface_data.title.clone(),
+        key: root_key,
+        children: Some(children),
+    }
+}
+
+// 未配置属性名风格时沿用旧规则，去掉字段名中的非法字符
+// 转换风格后的属性名与转换函数保持一致，不是合法标识符时由调用方加引号
+pub fn get_field_name(raw_name: &str, key_case: &KeyCase) -> String {
+    match key_case {
+        KeyCase::Original => get_legal_name(raw_name),
+        _ => convert_key(raw_name, key_case),
     }
 }
 
 fn get_name(value: &Value) -> String {
     match value.get("name") {
         Some(name) => match name.as_str() {
-            Some(name_str) => get_legal_name(name_str),
+            Some(name_str) => name_str.to_string(),
             None => String::from("unknowName"),
         },
         None => String::from("unknowName"),
@@ -0,0 +1,324 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::models::yapi::{
+    config::KeyCase,
+    interface::{Atom, JsonValue, ObjectLike, ObjectType, Root},
+};
+
+use super::{
+    common::{capitalize_first_letter, get_legal_name, get_path_arr},
+    key_case::convert_key,
+};
+
+const GO_HEADER: &str = "// Code generated by yapi-to-ts. DO NOT EDIT.\n";
+
+const GO_KEYWORDS: [&str; 25] = [
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+];
+
+struct GoField {
+    json_key: String,
+    name: String,
+    go_type: String,
+    required: bool,
+    description: String,
+}
+
+// 生成 go 文件内容，请求与响应的结构体写入同一个文件
+// 同一分类的接口写入同一个包，结构体名使用完整的接口路径以免重名
+pub fn get_go_file_string(package_name: &str, raw_path: &str, roots: &[&Root]) -> String {
+    let mut res_string = format!("{}\npackage {}\n", GO_HEADER, package_name);
+    let mut declared = HashMap::new();
+    let prefix = get_go_struct_prefix(raw_path);
+
+    for root in roots {
+        let struct_name = get_go_struct_name(&prefix, &root.key);
+        let values = root.children.clone().unwrap_or_default();
+
+        res_string += "\n";
+        resolve_structs(
+            &values,
+            &prefix,
+            &struct_name,
+            &root.interface_desc,
+            &mut declared,
+            &mut res_string,
+        );
+    }
+
+    res_string
+}
+
+// 接口路径转换为结构体名前缀：/api/user/info -> ApiUserInfo
+pub fn get_go_struct_prefix(raw_path: &str) -> String {
+    let prefix: String = get_path_arr(raw_path.to_string())
+        .iter()
+        .map(|p| capitalize_first_letter(&get_legal_name(&convert_key(p, &KeyCase::Camel))))
+        .collect();
+
+    match prefix.chars().next() {
+        None => String::from("Unknown"),
+        Some(c) if c.is_ascii_digit() => format!("Path{}", prefix),
+        Some(_) => prefix,
+    }
+}
+
+// 由分类名生成包名，分类名不含英文时使用接口路径的上一级目录
+pub fn get_go_package_name(category_name: Option<&str>, raw_path: &str, cat_id: u32) -> String {
+    let from_category = category_name.map(get_legal_package_name);
+    let path_arr = get_path_arr(raw_path.to_string());
+    let from_path = if path_arr.len() > 1 {
+        Some(get_legal_package_name(&path_arr[path_arr.len() - 2]))
+    } else {
+        None
+    };
+
+    from_category
+        .into_iter()
+        .chain(from_path)
+        .find(|name| is_legal_package_name(name))
+        .unwrap_or(format!("cat{}", cat_id))
+}
+
+// 从生成的内容中读取包名，用于决定写入的目录
+pub fn get_package_name_from_content(content: &str) -> Option<String> {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("package "))
+        .map(|name| name.trim().to_string())
+}
+
+// 接口路径转换为 go 文件名：/api/user/info -> api_user_info
+pub fn get_go_file_name(raw_path: String) -> String {
+    let file_name = get_path_arr(raw_path)
+        .iter()
+        .map(|p| {
+            convert_key(p, &KeyCase::Snake)
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("_");
+
+    if file_name.is_empty() {
+        String::from("unknown_file_name")
+    } else {
+        file_name
+    }
+}
+
+// 嵌套结构体先于引用它的结构体确定名称，输出时仍排在其后，返回实际声明的结构体名
+// declared 记录已声明的结构体名与结构，请求与响应共用
+fn resolve_structs(
+    values: &[JsonValue],
+    prefix: &str,
+    struct_name: &str,
+    description: &str,
+    declared: &mut HashMap<String, String>,
+    res_string: &mut String,
+) -> String {
+    let mut sub_string = String::new();
+    let mut fields = vec![];
+
+    for value in values {
+        let field = match value {
+            JsonValue::Atom(atom) => get_atom_field(atom),
+            JsonValue::ObjectLike(object_like) if object_like.nodes.is_empty() => {
+                get_object_like_field(object_like, None)
+            }
+            JsonValue::ObjectLike(object_like) => {
+                let sub_values: Vec<_> = object_like
+                    .nodes
+                    .iter()
+                    .map(|node| node.value.clone())
+                    .collect();
+                let sub_struct_name = resolve_structs(
+                    &sub_values,
+                    prefix,
+                    &get_go_struct_name(prefix, &object_like.key),
+                    &object_like.description,
+                    declared,
+                    &mut sub_string,
+                );
+                get_object_like_field(object_like, Some(&sub_struct_name))
+            }
+            JsonValue::Null => continue,
+        };
+        fields.push(field);
+    }
+    // user_id 与 userId 都会转换为 UserId，重名的字段加上序号
+    let mut field_names = HashSet::new();
+    for field in &mut fields {
+        let mut name = field.name.clone();
+        let mut index = 2;
+        while !field_names.insert(name.clone()) {
+            name = format!("{}{}", field.name, index);
+            index += 1;
+        }
+        field.name = name;
+    }
+
+    // 请求与响应的 data、不同层级的 items 会得到同名结构体，结构相同时只声明一次，不同时加上序号
+    let shape = fields
+        .iter()
+        .map(|field| format!("{} {}", field.json_key, field.go_type))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut index = 1;
+    let struct_name = loop {
+        let name = if index == 1 {
+            struct_name.to_string()
+        } else {
+            format!("{}{}", struct_name, index)
+        };
+        match declared.get(&name) {
+            Some(declared_shape) if *declared_shape == shape => return name,
+            Some(_) => index += 1,
+            None => break name,
+        }
+    };
+    declared.insert(struct_name.clone(), shape);
+
+    *res_string += &get_struct_go(&struct_name, description, &fields);
+    *res_string += &sub_string;
+
+    struct_name
+}
+
+fn get_atom_field(atom: &Atom) -> GoField {
+    let go_type = match (atom.value.as_str(), atom.format.as_deref()) {
+        ("string", _) => "string",
+        ("integer", Some("int32")) => "int32",
+        ("integer", _) => "int64",
+        ("number", Some("float")) => "float32",
+        ("number", Some("int32")) => "int32",
+        ("number", Some("int64")) => "int64",
+        ("number", _) => "float64",
+        ("boolean", _) => "bool",
+        _ => "interface{}",
+    };
+
+    GoField {
+        json_key: atom.key.clone(),
+        name: get_go_field_name(&atom.key),
+        go_type: get_optional_type(go_type.to_string(), atom.required),
+        required: atom.required,
+        description: atom.description.replace('\n', ""),
+    }
+}
+
+// struct_name 为嵌套对象实际声明的结构体名，没有子节点时为空
+fn get_object_like_field(object_like: &ObjectLike, struct_name: Option<&str>) -> GoField {
+    let go_type = match (&object_like.object_type, struct_name) {
+        (ObjectType::Array, None) => String::from("[]interface{}"),
+        (ObjectType::Array, Some(struct_name)) => format!("[]{}", struct_name),
+        (ObjectType::Object, None) => String::from("map[string]interface{}"),
+        (ObjectType::Object, Some(struct_name)) => {
+            get_optional_type(struct_name.to_string(), object_like.required)
+        }
+    };
+
+    GoField {
+        json_key: object_like.key.clone(),
+        name: get_go_field_name(&object_like.key),
+        go_type,
+        required: object_like.required,
+        description: object_like.description.clone(),
+    }
+}
+
+// 非必填字段使用指针类型，slice、map 与 interface{} 本身可以为 nil
+fn get_optional_type(go_type: String, required: bool) -> String {
+    if required || go_type == "interface{}" {
+        go_type
+    } else {
+        format!("*{}", go_type)
+    }
+}
+
+fn get_struct_go(struct_name: &str, description: &str, fields: &[GoField]) -> String {
+    let mut res_string = format!(
+        "// {} {}\ntype {} struct {{\n",
+        struct_name, description, struct_name
+    );
+    let name_width = fields.iter().map(|f| f.name.len()).max().unwrap_or(0);
+    let type_width = fields.iter().map(|f| f.go_type.len()).max().unwrap_or(0);
+
+    for field in fields {
+        let omitempty = if field.required { "" } else { ",omitempty" };
+        res_string += &format!(
+            "\t// {}\n\t{:name_width$} {:type_width$} `json:\"{}{}\"`\n",
+            field.description,
+            field.name,
+            field.go_type,
+            field.json_key,
+            omitempty,
+            name_width = name_width,
+            type_width = type_width
+        );
+    }
+
+    res_string + "}\n\n"
+}
+
+// 与 ts 接口名规则一致，以完整路径生成的前缀代替文件名
+fn get_go_struct_name(prefix: &str, key: &str) -> String {
+    format!(
+        "{}{}",
+        prefix,
+        capitalize_first_letter(&get_legal_name(key))
+    )
+}
+
+fn get_go_field_name(key: &str) -> String {
+    let name: String = convert_key(key, &KeyCase::Camel)
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect();
+
+    match name.chars().next() {
+        None => String::from("UnknownName"),
+        Some(c) if c.is_ascii_digit() => format!("Field{}", name),
+        Some(_) => capitalize_first_letter(&name),
+    }
+}
+
+fn get_legal_package_name(raw_name: &str) -> String {
+    raw_name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+fn is_legal_package_name(name: &str) -> bool {
+    match name.chars().next() {
+        Some(c) => c.is_ascii_alphabetic() && !GO_KEYWORDS.contains(&name),
+        None => false,
+    }
+}
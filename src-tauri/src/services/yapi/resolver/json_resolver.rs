@@ -1,10 +1,16 @@
 use serde_json::Value;
 
-use crate::models::yapi::interface::{
-    Atom, InterfaceData, JsonType, JsonValue, Node, ObjectLike, ObjectType, Root, WebType,
+use crate::models::yapi::{
+    config::KeyCase,
+    interface::{
+        Atom, InterfaceData, JsonType, JsonValue, Node, ObjectLike, ObjectType, Root, WebType,
+    },
 };
 
-use super::common::{get_desc, get_legal_name, get_path_arr};
+use super::{
+    common::{get_desc, get_path_arr, get_ts_interface_name},
+    key_case::convert_key,
+};
 
 // 生成 ts 字符串
 
@@ -12,35 +18,36 @@ pub fn get_ts_string(
     web_type: WebType,
     interface_data: &InterfaceData,
     json_value: &Value,
+    key_case: &KeyCase,
 ) -> String {
     let root = generate_root(web_type, interface_data, json_value);
-    get_root_ts(root)
+    get_root_ts(&root, key_case)
 }
 
-fn get_root_ts(root: Root) -> String {
+pub fn get_root_ts(root: &Root, key_case: &KeyCase) -> String {
     let ts_name = get_ts_interface_name(&root.interface_name, &root.key);
     let header = format!("// {}\n", root.interface_desc);
     let mut res_string = String::from(header);
 
-    if let Some(nodes) = root.children {
-        res_string = res_string + resolve_root_interfaces(&nodes, &ts_name).as_str();
+    if let Some(nodes) = &root.children {
+        res_string = res_string + resolve_root_interfaces(nodes, &ts_name, key_case).as_str();
     } else {
-        res_string = res_string + resolve_root_interfaces(&vec![], &ts_name).as_str();
+        res_string = res_string + resolve_root_interfaces(&vec![], &ts_name, key_case).as_str();
     }
 
     res_string
 }
 
-fn resolve_root_interfaces(nodes: &Vec<JsonValue>, ts_name: &str) -> String {
+fn resolve_root_interfaces(nodes: &Vec<JsonValue>, ts_name: &str, key_case: &KeyCase) -> String {
     let mut sub_list = Vec::new();
     let mut res_string = format!("export interface {} {{\n", ts_name);
 
     for node in nodes {
         let mut t = String::new();
         if let JsonValue::Atom(atom) = node {
-            t = get_atom_ts(atom);
+            t = get_atom_ts(atom, key_case);
         } else if let JsonValue::ObjectLike(object_like) = node {
-            t = get_object_like_ts(object_like);
+            t = get_object_like_ts(object_like, key_case);
             sub_list.push(object_like);
         }
 
@@ -53,7 +60,7 @@ fn resolve_root_interfaces(nodes: &Vec<JsonValue>, ts_name: &str) -> String {
     let resolved_sub_list = resolve_root_interfaces_sub_list(sub_list);
 
     for (json_values, ts_name) in resolved_sub_list {
-        res_string = res_string + &resolve_root_interfaces(&json_values, &ts_name);
+        res_string = res_string + &resolve_root_interfaces(&json_values, &ts_name, key_case);
     }
 
     res_string
@@ -71,12 +78,12 @@ fn resolve_root_interfaces_sub_list(sub_list: Vec<&ObjectLike>) -> Vec<(Vec<Json
         .collect()
 }
 
-fn get_atom_ts(atom: &Atom) -> String {
+fn get_atom_ts(atom: &Atom, key_case: &KeyCase) -> String {
     let required_symbol = if atom.required { "" } else { "?" };
     format!(
         "    // {}\n    {}{}: {}\n",
         atom.description.replace("\n", ""),
-        atom.key,
+        convert_key(&atom.key, key_case),
         required_symbol,
         format_atom_type(&atom.value)
     )
@@ -90,7 +97,7 @@ fn format_atom_type(value: &String) -> String {
     }
 }
 
-fn get_object_like_ts(object_like: &ObjectLike) -> String {
+fn get_object_like_ts(object_like: &ObjectLike, key_case: &KeyCase) -> String {
     let required_symbol = if object_like.required { "" } else { "?" };
     let object_name = get_ts_interface_name(&object_like.interface_name, &object_like.key);
     let array_symbol = if object_like.object_type == ObjectType::Array {
@@ -101,14 +108,18 @@ fn get_object_like_ts(object_like: &ObjectLike) -> String {
 
     format!(
         "    // {}\n    {}{}: {}{}\n",
-        object_like.description, object_like.key, required_symbol, object_name, array_symbol
+        object_like.description,
+        convert_key(&object_like.key, key_case),
+        required_symbol,
+        object_name,
+        array_symbol
     )
 }
 
 // -------------- 生成模型
 
 // 生成根节点模型
-fn generate_root(web_type: WebType, interface_data: &InterfaceData, json_value: &Value) -> Root {
+pub fn generate_root(web_type: WebType, interface_data: &InterfaceData, json_value: &Value) -> Root {
     let root_key = if web_type == WebType::Request {
         String::from("request")
     } else {
@@ -162,6 +173,7 @@ fn get_root_children(interface_name: String, json_value: &Value) -> Vec<JsonValu
                         required,
                         key,
                         description,
+                        format: get_format(value),
                     };
                     JsonValue::Atom(atom)
                 }
@@ -231,6 +243,7 @@ fn generate_nodes(json_value: &Value, interface_name: &str) -> Vec<Node> {
                                 required,
                                 key: key.clone(),
                                 description: description.clone(),
+                                format: get_format(value),
                             };
                             JsonValue::Atom(atom)
                         }
@@ -309,6 +322,14 @@ fn get_ts_type(value: &Value) -> String {
     }
 }
 
+// 数值类型的格式，如 int32、int64、float
+fn get_format(value: &Value) -> Option<String> {
+    value
+        .get("format")
+        .and_then(|format| format.as_str())
+        .map(|format| format.to_string())
+}
+
 fn get_name(raw_name: &str) -> String {
     if raw_name.is_empty() {
         String::from("unknownName")
@@ -324,21 +345,3 @@ fn get_model_interface_name(interface_data: &InterfaceData) -> String {
         .clone();
     file_name
 }
-
-// 拼接生成ts接口名字
-fn get_ts_interface_name(interface_name: &str, key: &str) -> String {
-    format!(
-        "{}{}",
-        get_legal_name(interface_name),
-        capitalize_first_letter(&get_legal_name(key))
-    )
-}
-
-// 大写第一个字符
-fn capitalize_first_letter(s: &str) -> String {
-    let mut c = s.chars();
-    match c.next() {
-        None => String::new(),
-        Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
-    }
-}
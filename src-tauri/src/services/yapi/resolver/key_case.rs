@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+
+use crate::models::yapi::{
+    config::KeyCase,
+    interface::{JsonValue, ObjectType, Root, WebType},
+};
+
+use super::common::{
+    capitalize_first_letter, get_ts_interface_name, get_ts_property_key, is_ts_identifier,
+};
+
+// 按配置的风格转换属性名
+pub fn convert_key(key: &str, key_case: &KeyCase) -> String {
+    match key_case {
+        KeyCase::Original => key.to_string(),
+        KeyCase::Camel => to_camel_case(key),
+        KeyCase::Snake => to_snake_case(key),
+    }
+}
+
+// 生成接口数据与线上字段之间的转换函数
+// 请求：toXxxRequestWire 把重命名后的属性转换回接口字段
+// 响应：fromXxxResponseWire 把接口字段转换为重命名后的属性
+pub fn get_wire_mapper_ts(web_type: WebType, root: &Root, key_case: &KeyCase) -> String {
+    let ts_name = get_ts_interface_name(&root.interface_name, &root.key);
+    let values = root.children.clone().unwrap_or_default();
+    let body = get_object_expr(&values, "data", &web_type, key_case, 1);
+
+    match web_type {
+        WebType::Request => format!(
+            "export const to{}Wire = (data: {}): Record<string, any> => ({});\n",
+            capitalize_first_letter(&ts_name),
+            ts_name,
+            body
+        ),
+        WebType::Response => format!(
+            "export const from{}Wire = (data: any): {} => ({});\n",
+            capitalize_first_letter(&ts_name),
+            ts_name,
+            body
+        ),
+    }
+}
+
+// 同一对象内的字段重命名后同名时无法生成合法的 interface，返回出错的字段
+// 如 Camel 风格下的 user_id 与 userId
+pub fn check_duplicate_keys<F>(root: &Root, rename: &F) -> Result<(), String>
+where
+    F: Fn(&str) -> String,
+{
+    let ts_name = get_ts_interface_name(&root.interface_name, &root.key);
+    check_object_keys(
+        root.children.as_deref().unwrap_or_default(),
+        &ts_name,
+        rename,
+    )
+}
+
+fn check_object_keys<F>(values: &[JsonValue], ts_name: &str, rename: &F) -> Result<(), String>
+where
+    F: Fn(&str) -> String,
+{
+    let mut keys: HashMap<String, &str> = HashMap::new();
+
+    for value in values {
+        let key = match value {
+            JsonValue::Atom(atom) => &atom.key,
+            JsonValue::ObjectLike(object_like) => {
+                let nodes: Vec<_> = object_like
+                    .nodes
+                    .iter()
+                    .map(|node| node.value.clone())
+                    .collect();
+                check_object_keys(
+                    &nodes,
+                    &get_ts_interface_name(&object_like.interface_name, &object_like.key),
+                    rename,
+                )?;
+                &object_like.key
+            }
+            JsonValue::Null => continue,
+        };
+
+        let renamed_key = rename(key);
+        if let Some(other_key) = keys.insert(renamed_key.clone(), key) {
+            return Err(format!(
+                "{} 中的字段 {} 与 {} 转换后都为 {}",
+                ts_name, other_key, key, renamed_key
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// 递归生成对象字面量，嵌套对象与数组沿用同一棵 JsonValue 树
+fn get_object_expr(
+    values: &[JsonValue],
+    source: &str,
+    web_type: &WebType,
+    key_case: &KeyCase,
+    depth: usize,
+) -> String {
+    let indent = "    ".repeat(depth);
+    let mut res_string = String::from("{\n");
+
+    for value in values {
+        let (key, expr) = match value {
+            JsonValue::Atom(atom) => {
+                let (source_key, target_key) = get_key_pair(&atom.key, web_type, key_case);
+                (target_key, get_property_access(source, &source_key))
+            }
+            JsonValue::ObjectLike(object_like) => {
+                let (source_key, target_key) = get_key_pair(&object_like.key, web_type, key_case);
+                let access = get_property_access(source, &source_key);
+                let nodes: Vec<_> = object_like
+                    .nodes
+                    .iter()
+                    .map(|node| node.value.clone())
+                    .collect();
+
+                let expr = if nodes.is_empty() {
+                    access
+                } else if object_like.object_type == ObjectType::Array {
+                    let item = format!("item{}", depth);
+                    format!(
+                        "{} == null ? {} : {}.map(({}) => ({}))",
+                        access,
+                        access,
+                        access,
+                        item,
+                        get_object_expr(&nodes, &item, web_type, key_case, depth + 1)
+                    )
+                } else {
+                    format!(
+                        "{} == null ? {} : {}",
+                        access,
+                        access,
+                        get_object_expr(&nodes, &access, web_type, key_case, depth + 1)
+                    )
+                };
+
+                (target_key, expr)
+            }
+            JsonValue::Null => continue,
+        };
+
+        res_string += &format!("{}{}: {},\n", indent, get_ts_property_key(&key), expr);
+    }
+
+    res_string + &"    ".repeat(depth - 1) + "}"
+}
+
+// 返回 (读取的属性名, 写入的属性名)
+fn get_key_pair(wire_key: &str, web_type: &WebType, key_case: &KeyCase) -> (String, String) {
+    let renamed_key = convert_key(wire_key, key_case);
+    match web_type {
+        WebType::Request => (renamed_key, wire_key.to_string()),
+        WebType::Response => (wire_key.to_string(), renamed_key),
+    }
+}
+
+fn get_property_access(source: &str, key: &str) -> String {
+    if is_ts_identifier(key) {
+        format!("{}.{}", source, key)
+    } else {
+        format!("{}[\"{}\"]", source, key)
+    }
+}
+
+// user_id -> userId，保留开头的下划线
+fn to_camel_case(key: &str) -> String {
+    let mut res = String::new();
+    let mut upper_next = false;
+
+    for c in key.chars() {
+        if c == '_' || c == '-' {
+            if res.chars().any(|c| c != '_') {
+                upper_next = true;
+            } else {
+                res.push('_');
+            }
+        } else if upper_next {
+            res.extend(c.to_uppercase());
+            upper_next = false;
+        } else {
+            res.push(c);
+        }
+    }
+
+    res
+}
+
+// userId -> user_id，HTTPServer -> http_server
+fn to_snake_case(key: &str) -> String {
+    let chars: Vec<char> = key.chars().collect();
+    let mut res = String::new();
+
+    for (index, c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            let prev = if index > 0 { chars.get(index - 1) } else { None };
+            let next = chars.get(index + 1);
+            let is_word_start = match prev {
+                Some(prev) if prev.is_ascii_lowercase() || prev.is_ascii_digit() => true,
+                Some(prev) if prev.is_ascii_uppercase() => {
+                    next.map_or(false, |next| next.is_ascii_lowercase())
+                }
+                _ => false,
+            };
+
+            if is_word_start {
+                res.push('_');
+            }
+            res.push(c.to_ascii_lowercase());
+        } else if *c == '-' {
+            res.push('_');
+        } else {
+            res.push(*c);
+        }
+    }
+
+    res
+}
@@ -1,3 +1,7 @@
 pub mod form_resolver;
 pub mod common;
-pub mod json_resolver;
\ No newline at end of file
+pub mod json_resolver;
+pub mod key_case;
+pub mod zod_resolver;
+pub mod dart_resolver;
+pub mod go_resolver;
\ No newline at end of file
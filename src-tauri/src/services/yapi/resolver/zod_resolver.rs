@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use crate::models::yapi::interface::{Atom, JsonValue, ObjectLike, ObjectType, Root};
+
+use super::common::{get_ts_interface_name, get_ts_property_key};
+
+pub const ZOD_IMPORT: &str = "import { z } from 'zod';\n";
+
+// 生成 zod schema 字符串，rename 与生成 interface 时的属性名规则保持一致
+// declared 记录已声明的 schema 名称与结构，同一文件内的请求与响应共用
+// infer_type 为 true 时为每个 schema 导出 z.infer 推导的类型，与 interface 同文件时不能导出同名类型
+pub fn get_root_schema_ts<F>(
+    root: &Root,
+    rename: &F,
+    declared: &mut HashMap<String, String>,
+    infer_type: bool,
+) -> String
+where
+    F: Fn(&str) -> String,
+{
+    let ts_name = get_ts_interface_name(&root.interface_name, &root.key);
+    let mut res_string = format!("// {}\n", root.interface_desc);
+    let values = root.children.clone().unwrap_or_default();
+
+    resolve_schemas(
+        &values,
+        &ts_name,
+        rename,
+        declared,
+        infer_type,
+        &mut res_string,
+    );
+
+    res_string
+}
+
+// 子 schema 需要先于引用它的 schema 声明，返回实际声明的名称
+fn resolve_schemas<F>(
+    values: &[JsonValue],
+    ts_name: &str,
+    rename: &F,
+    declared: &mut HashMap<String, String>,
+    infer_type: bool,
+    res_string: &mut String,
+) -> String
+where
+    F: Fn(&str) -> String,
+{
+    let mut fields = String::new();
+
+    for value in values {
+        let field = match value {
+            JsonValue::Atom(atom) => get_atom_schema(atom, rename),
+            JsonValue::ObjectLike(object_like) if object_like.nodes.is_empty() => {
+                get_object_like_schema(object_like, None, rename)
+            }
+            JsonValue::ObjectLike(object_like) => {
+                let sub_ts_name =
+                    get_ts_interface_name(&object_like.interface_name, &object_like.key);
+                let sub_values: Vec<_> = object_like
+                    .nodes
+                    .iter()
+                    .map(|node| node.value.clone())
+                    .collect();
+                let sub_ts_name = resolve_schemas(
+                    &sub_values,
+                    &sub_ts_name,
+                    rename,
+                    declared,
+                    infer_type,
+                    res_string,
+                );
+                get_object_like_schema(object_like, Some(&sub_ts_name), rename)
+            }
+            JsonValue::Null => continue,
+        };
+        fields += &field;
+    }
+
+    // 同名且结构相同的 schema 只声明一次，结构不同时加上序号，const 重复声明会报错
+    let shape = get_schema_shape(&fields);
+    let mut index = 1;
+    let ts_name = loop {
+        let name = if index == 1 {
+            ts_name.to_string()
+        } else {
+            format!("{}{}", ts_name, index)
+        };
+        match declared.get(&name) {
+            Some(declared_shape) if *declared_shape == shape => return name,
+            Some(_) => index += 1,
+            None => break name,
+        }
+    };
+    declared.insert(ts_name.clone(), shape);
+
+    *res_string += &format!(
+        "export const {} = z.object({{\n{}}});\n",
+        get_schema_name(&ts_name),
+        fields
+    );
+    if infer_type {
+        *res_string += &format!(
+            "export type {} = z.infer<typeof {}>;\n",
+            ts_name,
+            get_schema_name(&ts_name)
+        );
+    }
+
+    ts_name
+}
+
+// 比较结构时忽略字段注释
+fn get_schema_shape(fields: &str) -> String {
+    fields
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("//"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn get_atom_schema<F>(atom: &Atom, rename: &F) -> String
+where
+    F: Fn(&str) -> String,
+{
+    format!(
+        "    // {}\n    {}: {}{},\n",
+        atom.description.replace('\n', ""),
+        get_ts_property_key(&rename(&atom.key)),
+        format_atom_schema(&atom.value),
+        get_optional_symbol(atom.required)
+    )
+}
+
+// ts_name 为嵌套对象实际声明的 schema 对应的名称，没有子节点时为空
+fn get_object_like_schema<F>(object_like: &ObjectLike, ts_name: Option<&str>, rename: &F) -> String
+where
+    F: Fn(&str) -> String,
+{
+    let item_schema = match ts_name {
+        Some(ts_name) => get_schema_name(ts_name),
+        None => String::from("z.any()"),
+    };
+    let schema = if object_like.object_type == ObjectType::Array {
+        format!("z.array({})", item_schema)
+    } else {
+        item_schema
+    };
+
+    format!(
+        "    // {}\n    {}: {}{},\n",
+        object_like.description,
+        get_ts_property_key(&rename(&object_like.key)),
+        schema,
+        get_optional_symbol(object_like.required)
+    )
+}
+
+fn format_atom_schema(value: &str) -> String {
+    match value {
+        "string" => String::from("z.string()"),
+        "number" => String::from("z.number()"),
+        "integer" => String::from("z.number().int()"),
+        "boolean" => String::from("z.boolean()"),
+        "null" => String::from("z.null()"),
+        _ => String::from("z.any()"),
+    }
+}
+
+fn get_optional_symbol(required: bool) -> &'static str {
+    if required {
+        ""
+    } else {
+        ".optional()"
+    }
+}
+
+// userInfoResponse -> userInfoResponseSchema
+pub fn get_schema_name(ts_name: &str) -> String {
+    format!("{}Schema", ts_name)
+}
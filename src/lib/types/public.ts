@@ -7,6 +7,11 @@ export interface Config {
     header_template?: string
     file_name_template?: string
     type_import_template?: string
+    key_case?: 'original' | 'camel' | 'snake'
+    zod_mode?: 'inline' | 'sibling'
+    dart_path?: string
+    go_path?: string
+    manual_edit?: 'warn' | 'refuse' | 'overwrite'
 }
 
 export interface GlobalConfig {
@@ -14,6 +19,20 @@ export interface GlobalConfig {
     rate_limit?: number
     break_seconds?: number
     projects?: string[]
+    retry?: RetryPolicy
+    timeout_seconds?: number
+    connect_timeout_seconds?: number
+    headers?: Record<string, string>
+    ca_cert_path?: string
+    accept_invalid_certs?: boolean
+    page_size?: number
+}
+
+export interface RetryPolicy {
+    max_attempts: number
+    base_delay_ms: number
+    max_delay_ms: number
+    jitter_ms: number
 }
 
 
@@ -22,12 +41,24 @@ export interface ProjectList {
     project_id: string
     project_name:string
     categories: CategoryType[]
+    source?: ProjectSource
+    base_url?: string
+    proxy?: string
+    lock?: boolean
 }
 
+export type ProjectSource =
+    | { type: 'yapi' }
+    | { type: 'open_api', file_path: string }
+    | { type: 'yapi_dump', file_path: string }
+    | { type: 'har', file_path: string }
+    | { type: 'postman', file_path: string }
+
 export interface CategoryType {
     id: string
     name: string
     interfaces: InterfaceType[]
+    lock?: boolean
 }
 
 export interface InterfaceType {
@@ -35,6 +66,10 @@ export interface InterfaceType {
     name?: string
     path?: string
     lock?: boolean
+    up_time?: number
+    generated_up_time?: number
+    generated_path?: string
+    orphaned?: boolean
 }
 
 export interface SuccessResponse<T> {
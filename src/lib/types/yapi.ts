@@ -20,6 +20,7 @@ export type InterfaceDataItem = {
     catid: number,
     title: string,
     path: string,
+    up_time?: number,
     project_id?: number;
     // ts: string
 }
@@ -29,11 +30,25 @@ export type QueueLog = {
     processd_number: number,
     is_success: boolean,
     resolved_interface: ResolvedInterface
+    error?: YapiError
+    attempts: number
+}
+
+export type YapiError = {
+    kind: 'token_invalid' | 'no_permission' | 'not_found' | 'api' | 'http' | 'network' | 'request' | 'decode',
+    action: string,
+    status?: number,
+    errcode?: number,
+    errmsg: string,
+    attempts: number
 }
 
 export type ResolvedInterface = {
     interface: InterfaceData,
     ts_string: string,
+    schema_string?: string,
+    dart_string?: string,
+    go_string?: string,
 }
 
 export type InterfaceData = {
@@ -43,4 +58,164 @@ export type InterfaceData = {
     title: string,
     catid: number,
     method: string,
-}
\ No newline at end of file
+    up_time?: number,
+}
+
+export type CategoryFetchProgress = {
+    cat_id: number,
+    page: number,
+    total_pages: number,
+    fetched: number,
+    count: number
+}
+
+export type InterfaceChange = {
+    id: string,
+    cat_id: string,
+    name: string,
+    path?: string
+}
+
+export type InterfaceRename = {
+    id: string,
+    cat_id: string,
+    old_name: string,
+    new_name: string,
+    old_path?: string,
+    new_path?: string
+}
+
+export type ProjectDiscoveryReport = {
+    project_id: string,
+    added: InterfaceChange[],
+    renamed: InterfaceRename[],
+    removed: InterfaceChange[],
+    moved: InterfaceMove[]
+}
+
+export type InterfaceMove = {
+    id: string,
+    name: string,
+    old_cat_id: string,
+    new_cat_id: string,
+    old_path?: string,
+    new_path?: string
+}
+
+export type OrphanAction = 'delete' | 'deprecate'
+
+export type ReconcileReport = {
+    files: string[],
+    skipped: string[]
+}
+
+export type InterfaceSyncReport = {
+    project_id: string,
+    enqueued: number,
+    skipped: number,
+    locked: number,
+    discovery?: ProjectDiscoveryReport
+}
+
+export type RemoteInterfaceReport = {
+    project_id: string,
+    added: InterfaceChange[],
+    changed: InterfaceChange[],
+    renamed: InterfaceRename[],
+    ungenerated: InterfaceChange[],
+    enqueued: number
+}
+
+export type CacheStats = {
+    cache_dir: string,
+    entries: number,
+    bytes: number,
+    stale: number,
+    missing: number
+}
+
+export type CacheRegenerateReport = {
+    total: number,
+    resolved: number,
+    missing: string[],
+    failed: string[]
+}
+
+export type Provenance = {
+    project_id: number,
+    interface_id: number,
+    up_time?: number,
+    version: string,
+    hash: string
+}
+
+export type FieldChangeKind =
+    | 'added'
+    | 'removed'
+    | 'required_to_optional'
+    | 'optional_to_required'
+    | 'type_changed'
+    | 'interface_renamed'
+
+export type FieldChange = {
+    web_type: 'request' | 'response',
+    field: string,
+    kind: FieldChangeKind,
+    before?: string,
+    after?: string,
+    breaking: boolean
+}
+
+export type InterfaceDiff = {
+    project_id: string,
+    interface_id: string,
+    title: string,
+    method: string,
+    path: string,
+    is_new: boolean,
+    breaking: boolean,
+    changes: FieldChange[]
+}
+
+export type ChangeReport = {
+    interfaces: InterfaceDiff[],
+    breaking: number,
+    markdown_path?: string,
+    json_path?: string
+}
+
+export type PathRename = {
+    id: string,
+    name: string,
+    old_path: string,
+    new_path: string
+}
+
+export type PathRenameReport = {
+    renames: PathRename[],
+    moved: string[],
+    rewritten: string[],
+    skipped: string[]
+}
+
+export type HistoryEntry = {
+    version: string,
+    hash: string,
+    synced_at: number,
+    up_time?: number
+}
+
+export type FileDriftKind = 'stale' | 'missing' | 'extra'
+
+export type FileDrift = {
+    file_path: string,
+    kind: FileDriftKind,
+    diff?: string
+}
+
+export type CheckReport = {
+    checked: number,
+    drifts: FileDrift[],
+    failed: string[],
+    locked: string[]
+}